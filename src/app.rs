@@ -1,10 +1,19 @@
-use crate::events::{Actions, InputEvent};
-use crate::game_data::{GameData, GameDataError};
-use crate::hand::{Hand, HandOpError};
-use crate::play::{Play, PlayPhase};
-use crate::scoring::{scoring, Boxes};
+use crate::bot::{Bots, Decision, Difficulty, Hint, Seat};
+use crate::daily::{DailyChallenge, DailyLog, Date};
+use crate::events::{Actions, InputEvent, TICK_RATE};
+use crate::fair::{Exchange, FairDice, TranscriptFile};
+use crate::game_data::{GameData, GameDataError, GameSettings};
+use crate::hand::{Arrangement, DieSpec, HandOpError};
+use crate::odds::{BoxOdds, Odds};
+use crate::play::{Play, PlayPhase, Roller};
+use crate::review::Review;
+use crate::rule_set::Variant;
+use crate::scoring::Boxes;
+use crate::stats::{RollStats, StatsFile};
 use anyhow::{anyhow, bail, Result};
+use enum_iterator::Sequence;
 use std::fmt;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(PartialEq, Eq)]
@@ -24,13 +33,16 @@ pub enum PlayCursorPos {
     Roll,
     Hand(usize),
     Dust(usize),
-    Table(Boxes),
+    Table(Boxes, usize),
     Disappear,
 }
 
 #[derive(PartialEq, Eq)]
 pub enum StartMenuSelection {
     Play,
+    Daily,
+    Statistics,
+    Options,
     Exit,
 }
 
@@ -38,11 +50,84 @@ impl fmt::Display for StartMenuSelection {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             StartMenuSelection::Play => f.pad("Play"),
+            StartMenuSelection::Daily => f.pad("Daily"),
+            StartMenuSelection::Statistics => f.pad("Statistics"),
+            StartMenuSelection::Options => f.pad("Options"),
             StartMenuSelection::Exit => f.pad("Exit"),
         }
     }
 }
 
+#[derive(PartialEq, Eq, Sequence, Clone, Copy)]
+pub enum OptionsSelection {
+    RollsPerTurn,
+    CarryOverRolls,
+    PhysicalDice,
+    FairDice,
+    Dice,
+    Arrangement,
+    RollAnimation,
+    ReducedMotion,
+    Back,
+}
+
+impl fmt::Display for OptionsSelection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OptionsSelection::RollsPerTurn => f.pad("Rolls per turn"),
+            OptionsSelection::CarryOverRolls => f.pad("Carry over unused rolls"),
+            OptionsSelection::PhysicalDice => f.pad("Physical dice"),
+            OptionsSelection::FairDice => f.pad("Fair dice"),
+            OptionsSelection::Dice => f.pad("Dice"),
+            OptionsSelection::Arrangement => f.pad("Dice order"),
+            OptionsSelection::RollAnimation => f.pad("Roll animation"),
+            OptionsSelection::ReducedMotion => f.pad("Reduced motion"),
+            OptionsSelection::Back => f.pad("Back"),
+        }
+    }
+}
+
+/* How the dice are shown, which every game follows including the daily challenge */
+#[derive(Clone, Copy)]
+pub struct Preferences {
+    pub arrangement: Arrangement,
+    /* How long the dice tumble before they settle */
+    pub roll_animation: Duration,
+    /* Settle the dice at once without tumbling */
+    pub reduces_motion: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            arrangement: Arrangement::Stable,
+            roll_animation: Duration::from_millis(800),
+            reduces_motion: false,
+        }
+    }
+}
+
+impl Preferences {
+    pub const MIN_ROLL_ANIMATION: Duration = Duration::from_millis(200);
+    pub const MAX_ROLL_ANIMATION: Duration = Duration::from_millis(2000);
+    pub const ROLL_ANIMATION_STEP: Duration = Duration::from_millis(200);
+}
+
+#[derive(PartialEq, Eq)]
+pub enum RuleSetSelection {
+    Variant(Variant),
+    Back,
+}
+
+impl fmt::Display for RuleSetSelection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleSetSelection::Variant(v) => f.pad(&format!("{}", v)),
+            RuleSetSelection::Back => f.pad("Back"),
+        }
+    }
+}
+
 pub const LOWEST_PLAYER_ID: usize = 1;
 pub const HIGHEST_PLAYER_ID: usize = 4;
 
@@ -61,11 +146,34 @@ impl fmt::Display for NumPlayersSelection {
     }
 }
 
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum SeatsSelection {
+    Seat(usize),
+    Start,
+    Back,
+}
+
+impl fmt::Display for SeatsSelection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeatsSelection::Seat(pid) => f.pad(&format!("Player{}", pid)),
+            SeatsSelection::Start => f.pad("Start"),
+            SeatsSelection::Back => f.pad("Back"),
+        }
+    }
+}
+
 pub enum AppState {
     StartMenu(StartMenuSelection),
+    Options(OptionsSelection),
+    Statistics(&'static DieSpec),
+    SelectRuleSet(RuleSetSelection),
     SelectNumPlayers(NumPlayersSelection),
+    SelectSeats(SeatsSelection),
     Play(Option<Play>, PlayCursorPos),
     Result,
+    /* The index of the first choice on the screen */
+    Review(usize),
 }
 
 impl AppState {
@@ -73,10 +181,22 @@ impl AppState {
         Self::StartMenu(StartMenuSelection::Play)
     }
 
+    fn initialized_options_state() -> Self {
+        Self::Options(OptionsSelection::RollsPerTurn)
+    }
+
+    fn initialized_select_rule_set_state(variant: Variant) -> Self {
+        Self::SelectRuleSet(RuleSetSelection::Variant(variant))
+    }
+
     fn initialized_select_num_players_state() -> Self {
         Self::SelectNumPlayers(NumPlayersSelection::NumPlayers(LOWEST_PLAYER_ID))
     }
 
+    fn initialized_select_seats_state() -> Self {
+        Self::SelectSeats(SeatsSelection::Start)
+    }
+
     fn initialized_play_state() -> Self {
         Self::Play(None, PlayCursorPos::Disappear)
     }
@@ -99,11 +219,11 @@ impl AppState {
         }
     }
 
-    fn initialize_play_state(&mut self, player_id: usize) -> Result<()> {
+    fn initialize_play_state(&mut self, new_play: Play) -> Result<()> {
         match self {
             Self::Play(play, pos) => {
                 if play.is_none() {
-                    *play = Some(Play::new(player_id));
+                    *play = Some(new_play);
                     *pos = PlayCursorPos::Roll;
                     Ok(())
                 } else {
@@ -157,6 +277,34 @@ pub enum AppError {
 
 pub struct App {
     state: AppState,
+    variant: Variant,
+    settings: GameSettings,
+    preferences: Preferences,
+    seed: Option<u64>,
+    /* Where the other participants of fair dice post their contributions */
+    exchange: Option<Exchange>,
+    daily: Option<DailyChallenge>,
+    session_stats: RollStats,
+    /* An error if the file could not be loaded, which is then left as it is */
+    lifetime_stats: Result<RollStats>,
+    transcript_saved: bool,
+    /* Ticks elapsed since the dice started tumbling */
+    rolling_ticks: u32,
+    /* Who plays each seat of the next game */
+    seats: Vec<Seat>,
+    bots: Option<Bots>,
+    /* Ticks elapsed since the bot's last move */
+    bot_ticks: u32,
+    /* The rest of the move the bot has decided on in the current phase */
+    bot_decision: Option<Decision>,
+    shows_hints: bool,
+    shows_odds: bool,
+    /* The solver's advice for the current dice while hints are shown */
+    hint: Option<Hint>,
+    review: Option<Review>,
+    odds: Option<Odds>,
+    /* The odds of each box for the holds and the rolls left in the current turn */
+    turn_odds: Vec<BoxOdds>,
     game_data: Option<GameData>,
 }
 
@@ -170,15 +318,123 @@ impl App {
     pub fn new() -> Self {
         Self {
             state: AppState::StartMenu(StartMenuSelection::Play),
+            variant: Variant::Yahtzee,
+            settings: GameSettings::default(),
+            preferences: Preferences::default(),
+            seed: None,
+            exchange: None,
+            daily: None,
+            session_stats: RollStats::new(),
+            lifetime_stats: StatsFile::default().load(),
+            transcript_saved: false,
+            rolling_ticks: 0,
+            seats: vec![],
+            bots: None,
+            bot_ticks: 0,
+            bot_decision: None,
+            shows_hints: false,
+            shows_odds: true,
+            hint: None,
+            review: None,
+            odds: None,
+            turn_odds: vec![],
             game_data: None,
         }
     }
 
+    /* Games started by the app roll the same dice for the same seed */
+    pub fn with_options(seed: Option<u64>, exchange: Option<Exchange>) -> Self {
+        /* Passing an exchange asks for fair dice shared with the other participants */
+        let settings = GameSettings {
+            uses_fair_dice: exchange.is_some(),
+            ..GameSettings::default()
+        };
+        Self {
+            seed,
+            exchange,
+            settings,
+            ..Self::new()
+        }
+    }
+
     /* helper functions */
     pub fn get_state(&self) -> &AppState {
         &self.state
     }
 
+    pub fn get_settings(&self) -> &GameSettings {
+        &self.settings
+    }
+
+    pub fn get_preferences(&self) -> &Preferences {
+        &self.preferences
+    }
+
+    pub fn get_session_stats(&self) -> &RollStats {
+        &self.session_stats
+    }
+
+    pub fn get_lifetime_stats(&self) -> Result<&RollStats, &anyhow::Error> {
+        self.lifetime_stats.as_ref()
+    }
+
+    pub fn save_statistics(&self) -> Result<()> {
+        match &self.lifetime_stats {
+            Ok(stats) => StatsFile::default().save(stats),
+            Err(..) => Ok(()),
+        }
+    }
+
+    pub fn get_exchange(&self) -> Option<&Exchange> {
+        self.exchange.as_ref()
+    }
+
+    pub fn is_transcript_saved(&self) -> bool {
+        self.transcript_saved
+    }
+
+    pub fn get_daily(&self) -> Option<&DailyChallenge> {
+        self.daily.as_ref()
+    }
+
+    pub fn get_seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    /* Whether a bot waits for the solver to work out its moves */
+    pub fn is_bot_thinking(&self) -> bool {
+        self.bots.as_ref().is_some_and(|b| b.is_solving())
+    }
+
+    pub fn shows_hints(&self) -> bool {
+        self.shows_hints
+    }
+
+    pub fn shows_odds(&self) -> bool {
+        self.shows_odds
+    }
+
+    /* The solver gives hints for the games it can solve except the daily challenge */
+    pub fn can_hint(&self) -> bool {
+        self.daily.is_none() && self.can_review()
+    }
+
+    pub fn can_review(&self) -> bool {
+        self.bots.as_ref().is_some_and(|b| b.is_solvable())
+    }
+
+    pub fn get_review(&self) -> Option<&Review> {
+        self.review.as_ref()
+    }
+
+    pub fn get_hint(&self) -> Option<&Hint> {
+        self.hint.as_ref()
+    }
+
+    pub fn get_turn_odds(&self) -> &[BoxOdds] {
+        &self.turn_odds
+    }
+
     pub fn get_game_data(&self) -> Result<&GameData> {
         self.game_data
             .as_ref()
@@ -195,9 +451,14 @@ impl App {
     pub fn do_action(&mut self, input_event: InputEvent) -> Result<AppReturn> {
         Ok(match self.state {
             AppState::StartMenu(..) => self.do_action_in_start_menu(input_event)?,
+            AppState::Options(..) => self.do_action_in_options(input_event)?,
+            AppState::Statistics(..) => self.do_action_in_statistics(input_event)?,
+            AppState::SelectRuleSet(..) => self.do_action_in_select_rule_set(input_event)?,
             AppState::SelectNumPlayers(..) => self.do_action_in_select_num_players(input_event)?,
+            AppState::SelectSeats(..) => self.do_action_in_select_seats(input_event)?,
             AppState::Play(..) => self.do_action_in_play(input_event)?,
             AppState::Result => self.do_action_in_result(input_event)?,
+            AppState::Review(..) => self.do_action_in_review(input_event)?,
         })
     }
 
@@ -211,7 +472,35 @@ impl App {
                 };
                 match pos {
                     StartMenuSelection::Play => {
-                        self.state = AppState::initialized_select_num_players_state();
+                        self.state = AppState::initialized_select_rule_set_state(self.variant);
+                        AppReturn::Continue
+                    }
+                    StartMenuSelection::Daily => {
+                        /* Everyone plays the same solo game with the same dice on the same day */
+                        let date = Date::today();
+                        let settings = GameSettings::default();
+                        self.state = AppState::initialized_play_state();
+                        self.game_data = Some(GameData::new(
+                            1,
+                            Variant::Yahtzee.rule_set(),
+                            settings,
+                            date.seed(),
+                        ));
+                        self.daily = Some(DailyChallenge::new(date));
+                        self.odds = None;
+                        self.bots =
+                            Some(self.next_bots(
+                                date.seed(),
+                                Bots::can_solve(Variant::Yahtzee, &settings),
+                            ));
+                        AppReturn::Continue
+                    }
+                    StartMenuSelection::Statistics => {
+                        self.state = AppState::Statistics(self.settings.die);
+                        AppReturn::Continue
+                    }
+                    StartMenuSelection::Options => {
+                        self.state = AppState::initialized_options_state();
                         AppReturn::Continue
                     }
                     StartMenuSelection::Exit => AppReturn::Exit,
@@ -222,14 +511,13 @@ impl App {
                 let AppState::StartMenu(pos) = &mut self.state else {
                     panic!("Unexpected state")
                 };
-                match pos {
-                    StartMenuSelection::Play => {
-                        *pos = StartMenuSelection::Exit;
-                    }
-                    StartMenuSelection::Exit => {
-                        *pos = StartMenuSelection::Play;
-                    }
-                }
+                *pos = match pos {
+                    StartMenuSelection::Play => StartMenuSelection::Exit,
+                    StartMenuSelection::Daily => StartMenuSelection::Play,
+                    StartMenuSelection::Statistics => StartMenuSelection::Daily,
+                    StartMenuSelection::Options => StartMenuSelection::Statistics,
+                    StartMenuSelection::Exit => StartMenuSelection::Options,
+                };
                 AppReturn::Continue
             }
 
@@ -237,17 +525,202 @@ impl App {
                 let AppState::StartMenu(pos) = &mut self.state else {
                     panic!("Unexpected state")
                 };
+                *pos = match pos {
+                    StartMenuSelection::Play => StartMenuSelection::Daily,
+                    StartMenuSelection::Daily => StartMenuSelection::Statistics,
+                    StartMenuSelection::Statistics => StartMenuSelection::Options,
+                    StartMenuSelection::Options => StartMenuSelection::Exit,
+                    StartMenuSelection::Exit => StartMenuSelection::Play,
+                };
+                AppReturn::Continue
+            }
+
+            _ => AppReturn::Continue,
+        })
+    }
+
+    fn do_action_in_options(&mut self, input_event: InputEvent) -> Result<AppReturn> {
+        let AppState::Options(pos) = &mut self.state else {
+            panic!("Unexpected state")
+        };
+        let settings = &mut self.settings;
+        let preferences = &mut self.preferences;
+        Ok(match (input_event.action(), *pos) {
+            (Actions::Exit, ..) => AppReturn::Exit,
+
+            (Actions::Up, ..) => {
+                *pos = enum_iterator::previous_cycle(pos).unwrap();
+                AppReturn::Continue
+            }
+
+            (Actions::Down, ..) => {
+                *pos = enum_iterator::next_cycle(pos).unwrap();
+                AppReturn::Continue
+            }
+
+            (Actions::Left, OptionsSelection::RollsPerTurn) => {
+                if settings.rolls_per_turn > GameSettings::MIN_ROLLS_PER_TURN {
+                    settings.rolls_per_turn -= 1;
+                }
+                AppReturn::Continue
+            }
+
+            (Actions::Right, OptionsSelection::RollsPerTurn) => {
+                if settings.rolls_per_turn < GameSettings::MAX_ROLLS_PER_TURN {
+                    settings.rolls_per_turn += 1;
+                }
+                AppReturn::Continue
+            }
+
+            (
+                Actions::Select | Actions::Left | Actions::Right,
+                OptionsSelection::CarryOverRolls,
+            ) => {
+                settings.carries_over_rolls = !settings.carries_over_rolls;
+                AppReturn::Continue
+            }
+
+            /* Typed pips cannot be committed to in advance, so the two are exclusive */
+            (Actions::Select | Actions::Left | Actions::Right, OptionsSelection::PhysicalDice) => {
+                settings.uses_physical_dice = !settings.uses_physical_dice;
+                settings.uses_fair_dice &= !settings.uses_physical_dice;
+                AppReturn::Continue
+            }
+
+            (Actions::Select | Actions::Left | Actions::Right, OptionsSelection::FairDice) => {
+                settings.uses_fair_dice = !settings.uses_fair_dice;
+                settings.uses_physical_dice &= !settings.uses_fair_dice;
+                AppReturn::Continue
+            }
+
+            (Actions::Left, OptionsSelection::Dice) => {
+                settings.die = settings.die.previous_cycle();
+                AppReturn::Continue
+            }
+
+            (Actions::Right, OptionsSelection::Dice) => {
+                settings.die = settings.die.next_cycle();
+                AppReturn::Continue
+            }
+
+            (Actions::Left, OptionsSelection::Arrangement) => {
+                preferences.arrangement =
+                    enum_iterator::previous_cycle(&preferences.arrangement).unwrap();
+                AppReturn::Continue
+            }
+
+            (Actions::Right, OptionsSelection::Arrangement) => {
+                preferences.arrangement =
+                    enum_iterator::next_cycle(&preferences.arrangement).unwrap();
+                AppReturn::Continue
+            }
+
+            (Actions::Left, OptionsSelection::RollAnimation) => {
+                if preferences.roll_animation > Preferences::MIN_ROLL_ANIMATION {
+                    preferences.roll_animation -= Preferences::ROLL_ANIMATION_STEP;
+                }
+                AppReturn::Continue
+            }
+
+            (Actions::Right, OptionsSelection::RollAnimation) => {
+                if preferences.roll_animation < Preferences::MAX_ROLL_ANIMATION {
+                    preferences.roll_animation += Preferences::ROLL_ANIMATION_STEP;
+                }
+                AppReturn::Continue
+            }
+
+            (Actions::Select | Actions::Left | Actions::Right, OptionsSelection::ReducedMotion) => {
+                preferences.reduces_motion = !preferences.reduces_motion;
+                AppReturn::Continue
+            }
+
+            (Actions::Select, OptionsSelection::Back) => {
+                self.state = AppState::StartMenu(StartMenuSelection::Options);
+                AppReturn::Continue
+            }
+
+            _ => AppReturn::Continue,
+        })
+    }
+
+    fn do_action_in_statistics(&mut self, input_event: InputEvent) -> Result<AppReturn> {
+        let AppState::Statistics(die) = &mut self.state else {
+            panic!("Unexpected state")
+        };
+        Ok(match input_event.action() {
+            Actions::Exit => AppReturn::Exit,
+
+            Actions::Left => {
+                *die = die.previous_cycle();
+                AppReturn::Continue
+            }
+
+            Actions::Right => {
+                *die = die.next_cycle();
+                AppReturn::Continue
+            }
+
+            Actions::Select => {
+                self.state = AppState::StartMenu(StartMenuSelection::Statistics);
+                AppReturn::Continue
+            }
+
+            _ => AppReturn::Continue,
+        })
+    }
+
+    fn do_action_in_select_rule_set(&mut self, input_event: InputEvent) -> Result<AppReturn> {
+        Ok(match input_event.action() {
+            Actions::Exit => AppReturn::Exit,
+
+            Actions::Select => {
+                let AppState::SelectRuleSet(pos) = &self.state else {
+                    panic!("Unexpected state")
+                };
                 match pos {
-                    StartMenuSelection::Play => {
-                        *pos = StartMenuSelection::Exit;
+                    &RuleSetSelection::Variant(variant) => {
+                        self.variant = variant;
+                        self.state = AppState::initialized_select_num_players_state();
                     }
-                    StartMenuSelection::Exit => {
-                        *pos = StartMenuSelection::Play;
+                    RuleSetSelection::Back => {
+                        self.state = AppState::initialized_start_menu_state();
                     }
                 }
                 AppReturn::Continue
             }
 
+            Actions::Up => {
+                let AppState::SelectRuleSet(pos) = &mut self.state else {
+                    panic!("Unexpected state")
+                };
+                *pos = match pos {
+                    RuleSetSelection::Variant(variant) => match enum_iterator::previous(variant) {
+                        Some(prev) => RuleSetSelection::Variant(prev),
+                        None => RuleSetSelection::Back,
+                    },
+                    RuleSetSelection::Back => {
+                        RuleSetSelection::Variant(enum_iterator::last::<Variant>().unwrap())
+                    }
+                };
+                AppReturn::Continue
+            }
+
+            Actions::Down => {
+                let AppState::SelectRuleSet(pos) = &mut self.state else {
+                    panic!("Unexpected state")
+                };
+                *pos = match pos {
+                    RuleSetSelection::Variant(variant) => match enum_iterator::next(variant) {
+                        Some(next) => RuleSetSelection::Variant(next),
+                        None => RuleSetSelection::Back,
+                    },
+                    RuleSetSelection::Back => {
+                        RuleSetSelection::Variant(enum_iterator::first::<Variant>().unwrap())
+                    }
+                };
+                AppReturn::Continue
+            }
+
             _ => AppReturn::Continue,
         })
     }
@@ -262,11 +735,12 @@ impl App {
                 };
                 match pos {
                     &NumPlayersSelection::NumPlayers(num_players) => {
-                        self.state = AppState::initialized_play_state();
-                        self.game_data = Some(GameData::new(num_players));
+                        /* The seats keep who played them in the last game */
+                        self.seats.resize(num_players, Seat::Human);
+                        self.state = AppState::initialized_select_seats_state();
                     }
                     NumPlayersSelection::Back => {
-                        self.state = AppState::initialized_start_menu_state();
+                        self.state = AppState::initialized_select_rule_set_state(self.variant);
                     }
                 }
                 AppReturn::Continue
@@ -315,11 +789,92 @@ impl App {
         })
     }
 
+    fn do_action_in_select_seats(&mut self, input_event: InputEvent) -> Result<AppReturn> {
+        let AppState::SelectSeats(pos) = &mut self.state else {
+            panic!("Unexpected state")
+        };
+        let num_players = self.seats.len();
+        Ok(match (input_event.action(), *pos) {
+            (Actions::Exit, ..) => AppReturn::Exit,
+
+            (Actions::Up, ..) => {
+                *pos = match *pos {
+                    SeatsSelection::Seat(0) => SeatsSelection::Back,
+                    SeatsSelection::Seat(pid) => SeatsSelection::Seat(pid - 1),
+                    SeatsSelection::Start => SeatsSelection::Seat(num_players - 1),
+                    SeatsSelection::Back => SeatsSelection::Start,
+                };
+                AppReturn::Continue
+            }
+
+            (Actions::Down, ..) => {
+                *pos = match *pos {
+                    SeatsSelection::Seat(pid) if pid + 1 < num_players => {
+                        SeatsSelection::Seat(pid + 1)
+                    }
+                    SeatsSelection::Seat(..) => SeatsSelection::Start,
+                    SeatsSelection::Start => SeatsSelection::Back,
+                    SeatsSelection::Back => SeatsSelection::Seat(0),
+                };
+                AppReturn::Continue
+            }
+
+            (Actions::Left, SeatsSelection::Seat(pid)) => {
+                self.seats[pid] = enum_iterator::previous_cycle(&self.seats[pid]).unwrap();
+                AppReturn::Continue
+            }
+
+            (Actions::Select | Actions::Right, SeatsSelection::Seat(pid)) => {
+                self.seats[pid] = enum_iterator::next_cycle(&self.seats[pid]).unwrap();
+                AppReturn::Continue
+            }
+
+            (Actions::Select, SeatsSelection::Start) => {
+                let seed = self.seed.unwrap_or_else(rand::random);
+                let mut bots = self.next_bots(seed, Bots::can_solve(self.variant, &self.settings));
+                if self.seats.contains(&Seat::Bot(Difficulty::NearOptimal)) {
+                    bots.start_solving();
+                }
+                self.state = AppState::initialized_play_state();
+                self.game_data = Some(GameData::with_seats(
+                    self.seats.clone(),
+                    self.variant.rule_set(),
+                    self.settings,
+                    seed,
+                ));
+                self.daily = None;
+                self.odds = None;
+                self.bots = Some(bots);
+                AppReturn::Continue
+            }
+
+            (Actions::Select, SeatsSelection::Back) => {
+                self.state =
+                    AppState::SelectNumPlayers(NumPlayersSelection::NumPlayers(num_players));
+                AppReturn::Continue
+            }
+
+            _ => AppReturn::Continue,
+        })
+    }
+
     fn do_action_in_play(&mut self, input_event: InputEvent) -> Result<AppReturn> {
+        let is_tick = matches!(input_event, InputEvent::Tick);
+        let ret = match self.bot_to_move()? {
+            Some(difficulty) => self.do_action_in_bot_turn(difficulty, input_event)?,
+            None => self.do_action_in_play_phase(input_event)?,
+        };
+        self.update_hint(is_tick)?;
+        self.update_odds()?;
+        Ok(ret)
+    }
+
+    fn do_action_in_play_phase(&mut self, input_event: InputEvent) -> Result<AppReturn> {
         Ok(match self.state.get_play_data() {
             Ok(play) => match play.get_phase() {
                 PlayPhase::Init => self.do_action_in_init(input_event)?,
                 PlayPhase::Roll(..) => self.do_action_in_roll(input_event)?,
+                PlayPhase::Announce => self.do_action_in_announce(input_event)?,
                 PlayPhase::SelectOrReroll(..) => self.do_action_in_select_or_reroll(input_event)?,
                 PlayPhase::Select => self.do_action_in_select(input_event)?,
             },
@@ -336,9 +891,43 @@ impl App {
 
             _ => {
                 match self.get_game_data()?.current_player_id() {
-                    Ok(pid) => self.state.initialize_play_state(pid)?,
+                    Ok(pid) => {
+                        let game_data = self.get_game_data()?;
+                        let play = Play::new(
+                            pid,
+                            game_data.get_rule_set().dice_num(),
+                            game_data.get_settings().die,
+                            game_data.get_roll_budget(pid),
+                            game_data.get_score_table(pid).has_announce_column(),
+                            if game_data.get_settings().uses_physical_dice {
+                                Roller::Manual(vec![])
+                            } else if game_data.get_settings().uses_fair_dice {
+                                Roller::Fair(Box::new(match &self.exchange {
+                                    Some(exchange) => FairDice::with_exchange(
+                                        exchange.clone(),
+                                        game_data.get_transcript().len(),
+                                    ),
+                                    None => FairDice::new(game_data.get_num_players()),
+                                }))
+                            } else {
+                                Roller::Random(Box::new(game_data.turn_rng()))
+                            },
+                            self.preferences.arrangement,
+                        )?;
+                        self.state.initialize_play_state(play)?
+                    }
                     Err(e) => match e.downcast_ref::<GameDataError>() {
-                        Some(GameDataError::FinishedGame) => self.state = AppState::Result,
+                        Some(GameDataError::FinishedGame) => {
+                            self.finish_daily_challenge()?;
+                            /* The statistics are saved again on exit if this fails */
+                            let _ = self.save_statistics();
+                            self.save_fair_transcript()?;
+                            /* The review is worked out while the results are on the screen */
+                            if let Some(bots) = &mut self.bots {
+                                bots.start_solving();
+                            }
+                            self.state = AppState::Result
+                        }
                         _ => return Err(e),
                     },
                 }
@@ -347,13 +936,37 @@ impl App {
         })
     }
 
+    fn save_fair_transcript(&mut self) -> Result<()> {
+        let game_data = self.get_game_data()?;
+        if game_data.get_settings().uses_fair_dice {
+            /* The other participants check their reveals against the copy in the exchange */
+            let file = match &self.exchange {
+                Some(exchange) => exchange.transcript_file(),
+                None => TranscriptFile::default(),
+            };
+            /* The result screen tells the player when the transcript is unavailable */
+            self.transcript_saved = file
+                .save(game_data.get_settings().die, game_data.get_transcript())
+                .is_ok();
+        }
+        Ok(())
+    }
+
+    fn finish_daily_challenge(&mut self) -> Result<()> {
+        let score = self.get_game_data()?.get_score_table(0).get_total_score();
+        if let Some(daily) = &mut self.daily {
+            /* The result screen tells the player when the log is unavailable */
+            let _ = daily.finish(&DailyLog::default(), score);
+        }
+        Ok(())
+    }
+
     fn do_action_in_init(&mut self, input_event: InputEvent) -> Result<AppReturn> {
         Ok(match input_event.action() {
             Actions::Exit => AppReturn::Exit,
 
             Actions::Select => {
-                let play = self.state.get_mut_play_data()?;
-                play.progress()?;
+                self.start_rolling()?;
                 AppReturn::Continue
             }
 
@@ -361,11 +974,63 @@ impl App {
         })
     }
 
-    fn move_cursor_pos_to_table(&mut self) -> Result<()> {
-        let pid = self.state.get_mut_play_data()?.get_player_id();
-        for pos in enum_iterator::all::<Boxes>() {
-            if !self.get_game_data()?.get_score_table(pid).has_score_in(pos) {
-                *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Table(pos);
+    /* Whether the cursor can stop at the box; in the announce phase only announceable boxes are. */
+    fn is_selectable_box(&self, col: usize, b: Boxes) -> Result<bool> {
+        let play = self.state.get_play_data()?;
+        let pid = play.get_player_id();
+        let score_table = self.get_game_data()?.get_score_table(pid);
+        Ok(match play.get_phase() {
+            PlayPhase::Announce => score_table.can_announce(col, b),
+            _ => {
+                let dice = play.get_hand().get_dice()?;
+                score_table.can_fill(col, b, &dice)
+            }
+        })
+    }
+
+    fn find_fillable_box_in_column(&self, col: usize, preferred: Boxes) -> Result<Option<Boxes>> {
+        if self.is_selectable_box(col, preferred)? {
+            return Ok(Some(preferred));
+        }
+
+        for &b in self.get_game_data()?.get_rule_set().boxes() {
+            if self.is_selectable_box(col, b)? {
+                return Ok(Some(b));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn move_cursor_pos_for_phase(&mut self) -> Result<()> {
+        let play = self.state.get_play_data()?;
+        let pid = play.get_player_id();
+        match play.get_phase() {
+            PlayPhase::Announce => {
+                if self.get_game_data()?.get_score_table(pid).must_announce() {
+                    self.move_cursor_pos_to_table()?;
+                } else {
+                    *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Roll;
+                }
+            }
+            PlayPhase::SelectOrReroll(..) => {
+                *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Hand(0);
+            }
+            PlayPhase::Select => {
+                self.move_cursor_pos_to_table()?;
+            }
+            _ => panic!("Unexpected PlayPhase"),
+        }
+
+        Ok(())
+    }
+
+    fn move_cursor_pos_to_table(&mut self) -> Result<()> {
+        let rule = self.get_game_data()?.get_rule_set();
+        let first = rule.boxes()[0];
+        for col in 0..rule.columns().len() {
+            if let Some(pos) = self.find_fillable_box_in_column(col, first)? {
+                *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Table(pos, col);
                 break;
             }
         }
@@ -377,81 +1042,233 @@ impl App {
         Ok(match input_event.action() {
             Actions::Exit => AppReturn::Exit,
 
+            /* Skips the animation as well as confirms the typed pips */
             Actions::Select => {
-                let play = self.state.get_mut_play_data()?;
+                if self.state.get_play_data()?.is_entry_complete() {
+                    self.settle_dice()?;
+                }
+                AppReturn::Continue
+            }
 
-                play.progress()?;
-                match play.get_phase() {
-                    PlayPhase::SelectOrReroll(..) => {
-                        *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Hand(0);
-                    }
-                    PlayPhase::Select => {
-                        self.move_cursor_pos_to_table()?;
+            Actions::Number(pip) if self.state.get_play_data()?.is_manual() => {
+                self.state.get_mut_play_data()?.enter_pip(pip)?;
+                AppReturn::Continue
+            }
+
+            Actions::Erase if self.state.get_play_data()?.is_manual() => {
+                self.state.get_mut_play_data()?.erase_pip()?;
+                AppReturn::Continue
+            }
+
+            _ => {
+                if matches!(input_event, InputEvent::Tick)
+                    && !self.state.get_play_data()?.is_manual()
+                {
+                    self.tumble_dice()?;
+                }
+                AppReturn::Continue
+            }
+        })
+    }
+
+    fn start_rolling(&mut self) -> Result<()> {
+        let play = self.state.get_mut_play_data()?;
+        play.progress()?;
+        play.exchange_contributions()?;
+        self.rolling_ticks = 0;
+        let play = self.state.get_play_data()?;
+        if self.preferences.reduces_motion && !play.is_manual() && play.is_entry_complete() {
+            self.settle_dice()?;
+        }
+        Ok(())
+    }
+
+    /* The unheld dice show another face every few ticks until the animation time is up */
+    fn tumble_dice(&mut self) -> Result<()> {
+        const TICKS_PER_FACE: u32 = 3;
+
+        self.rolling_ticks += 1;
+        let preferences = self.preferences;
+        /* The dice keep tumbling while the fair dice wait for the other participants */
+        let play = self.state.get_mut_play_data()?;
+        play.exchange_contributions()?;
+        let is_over = preferences.reduces_motion
+            || self.rolling_ticks * TICK_RATE >= preferences.roll_animation;
+        if is_over && play.is_entry_complete() {
+            return self.settle_dice();
+        }
+        if self.rolling_ticks.is_multiple_of(TICKS_PER_FACE) && !preferences.reduces_motion {
+            self.state.get_mut_play_data()?.shake_dice()?;
+        }
+        Ok(())
+    }
+
+    fn settle_dice(&mut self) -> Result<()> {
+        let play = self.state.get_mut_play_data()?;
+        play.progress()?;
+        /* Only the dice from the random source are worth auditing, and seeded ones repeat */
+        if !play.is_manual() && self.seed.is_none() {
+            self.session_stats.record(play.get_hand())?;
+            if let Ok(stats) = &mut self.lifetime_stats {
+                stats.record(play.get_hand())?;
+            }
+        }
+        self.move_cursor_pos_for_phase()
+    }
+
+    fn do_action_in_announce(&mut self, input_event: InputEvent) -> Result<AppReturn> {
+        let pid = self.state.get_play_data()?.get_player_id();
+        let must_announce = self.get_game_data()?.get_score_table(pid).must_announce();
+        Ok(match input_event.action() {
+            Actions::Exit => AppReturn::Exit,
+
+            Actions::Select => {
+                self.announce_action()?;
+                AppReturn::Continue
+            }
+
+            Actions::Left => {
+                if let PlayCursorPos::Table(..) = self.state.get_play_cursor_pos()? {
+                    let has_moved = self.left_action_in_score_table()?;
+                    if !has_moved && !must_announce {
+                        *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Roll;
                     }
-                    _ => panic!("Unexpected PlayPhase"),
                 }
+                AppReturn::Continue
+            }
 
+            Actions::Right => {
+                match self.state.get_play_cursor_pos()? {
+                    PlayCursorPos::Roll => self.move_cursor_pos_to_table()?,
+                    PlayCursorPos::Table(..) => self.right_action_in_score_table()?,
+                    _ => (),
+                }
                 AppReturn::Continue
             }
 
-            _ => {
-                self.state
-                    .get_mut_play_data()?
-                    .get_mut_hand()
-                    .reroll_dice()?;
+            Actions::Up => {
+                if let PlayCursorPos::Table(..) = self.state.get_play_cursor_pos()? {
+                    self.up_action_in_score_table()?;
+                }
                 AppReturn::Continue
             }
+
+            Actions::Down => {
+                if let PlayCursorPos::Table(..) = self.state.get_play_cursor_pos()? {
+                    self.down_action_in_score_table()?;
+                }
+                AppReturn::Continue
+            }
+
+            _ => AppReturn::Continue,
         })
     }
 
+    /* Announces the box under the cursor, or skips the announcement on the Roll button */
+    fn announce_action(&mut self) -> Result<()> {
+        let pid = self.state.get_play_data()?.get_player_id();
+        match self.state.get_play_cursor_pos()? {
+            PlayCursorPos::Roll => {}
+            &PlayCursorPos::Table(b, col) => {
+                self.get_mut_game_data()?
+                    .get_mut_score_table(pid)
+                    .announce(col, b)?;
+            }
+            _ => return Ok(()),
+        }
+        self.state.get_mut_play_data()?.progress()?;
+        self.move_cursor_pos_for_phase()
+    }
+
     fn up_action_in_score_table(&mut self) -> Result<()> {
-        let &PlayCursorPos::Table(pos) = self.state.get_play_cursor_pos()? else {
+        let &PlayCursorPos::Table(pos, col) = self.state.get_play_cursor_pos()? else {
             bail!(PlayCursorPosError::NotInTable);
         };
 
-        let mut p = pos;
-        let pid = self.state.get_play_data()?.get_player_id();
-        let score_table = self.get_game_data()?.get_score_table(pid);
-        while let Some(prev) = enum_iterator::previous_cycle(&p) {
-            if !score_table.has_score_in(prev) || prev == pos {
-                *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Table(prev);
+        let boxes = self.get_game_data()?.get_rule_set().boxes();
+        let cur = boxes.iter().position(|&b| b == pos).unwrap();
+        for i in 1..=boxes.len() {
+            let prev = boxes[(cur + boxes.len() - i) % boxes.len()];
+            if prev == pos || self.is_selectable_box(col, prev)? {
+                *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Table(prev, col);
                 break;
             }
-            p = prev;
         }
 
         Ok(())
     }
 
     fn down_action_in_score_table(&mut self) -> Result<()> {
-        let &PlayCursorPos::Table(pos) = self.state.get_play_cursor_pos()? else {
+        let &PlayCursorPos::Table(pos, col) = self.state.get_play_cursor_pos()? else {
             bail!(PlayCursorPosError::NotInTable);
         };
 
-        let mut p = pos;
-        let pid = self.state.get_play_data()?.get_player_id();
-        let score_table = self.get_game_data()?.get_score_table(pid);
-        while let Some(next) = enum_iterator::next_cycle(&p) {
-            if !score_table.has_score_in(next) || next == pos {
-                *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Table(next);
+        let boxes = self.get_game_data()?.get_rule_set().boxes();
+        let cur = boxes.iter().position(|&b| b == pos).unwrap();
+        for i in 1..=boxes.len() {
+            let next = boxes[(cur + i) % boxes.len()];
+            if next == pos || self.is_selectable_box(col, next)? {
+                *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Table(next, col);
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /* Moves to the nearest column on the left with a fillable box; false if there is none. */
+    fn left_action_in_score_table(&mut self) -> Result<bool> {
+        let &PlayCursorPos::Table(pos, col) = self.state.get_play_cursor_pos()? else {
+            bail!(PlayCursorPosError::NotInTable);
+        };
+
+        for prev in (0..col).rev() {
+            if let Some(b) = self.find_fillable_box_in_column(prev, pos)? {
+                *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Table(b, prev);
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn right_action_in_score_table(&mut self) -> Result<()> {
+        let &PlayCursorPos::Table(pos, col) = self.state.get_play_cursor_pos()? else {
+            bail!(PlayCursorPosError::NotInTable);
+        };
+
+        let num_columns = self.get_game_data()?.get_rule_set().columns().len();
+        for next in col + 1..num_columns {
+            if let Some(b) = self.find_fillable_box_in_column(next, pos)? {
+                *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Table(b, next);
                 break;
             }
-            p = next;
         }
 
         Ok(())
     }
 
     fn confirm_score_action(&mut self) -> Result<()> {
-        let &PlayCursorPos::Table(pos) = self.state.get_play_cursor_pos()? else {
+        let &PlayCursorPos::Table(pos, col) = self.state.get_play_cursor_pos()? else {
             bail!(PlayCursorPosError::NotInTable);
         };
 
         let play = self.state.get_play_data()?;
         let pid = play.get_player_id();
-        let dice = HandOpError::unwrap_pips(play.get_hand().get_pips());
-        let score_table = self.get_mut_game_data()?.get_mut_score_table(pid);
-        score_table.confirm_score(pos, scoring(pos, &dice))?;
+        let dice = play.get_hand().get_dice()?;
+        let remaining_rolls = play.get_remaining_rolls();
+        let turn = play.get_turn_context();
+        let records = play.get_fair_transcript().map(|r| r.to_vec());
+        let record = play.record_turn(pos)?;
+        let game_data = self.get_mut_game_data()?;
+        if let Some(records) = records {
+            game_data.extend_transcript(&records);
+        }
+        game_data
+            .get_mut_score_table(pid)
+            .confirm_dice(col, pos, &dice, &turn)?;
+        game_data.record_turn(record);
+        game_data.save_rolls(pid, remaining_rolls);
         self.state.cleanup_play_data()?;
 
         Ok(())
@@ -463,16 +1280,13 @@ impl App {
 
             Actions::Select => {
                 match self.state.get_play_cursor_pos()? {
-                    PlayCursorPos::Roll => {
-                        let play = self.state.get_mut_play_data()?;
-                        match play.progress() {
-                            Ok(..) => (),
-                            Err(e) => match e.downcast_ref::<HandOpError>() {
-                                Some(HandOpError::NoDiceToRoll) => (),
-                                _ => return Err(e),
-                            },
-                        }
-                    }
+                    PlayCursorPos::Roll => match self.start_rolling() {
+                        Ok(..) => (),
+                        Err(e) => match e.downcast_ref::<HandOpError>() {
+                            Some(HandOpError::NoDiceToRoll) => (),
+                            _ => return Err(e),
+                        },
+                    },
                     &PlayCursorPos::Hand(pos) | &PlayCursorPos::Dust(pos) => {
                         let hand = self.state.get_mut_play_data()?.get_mut_hand();
                         hand.hold(pos, !hand.is_held(pos)?)?;
@@ -486,15 +1300,18 @@ impl App {
             }
 
             Actions::Left => {
+                let dice_num = self.state.get_play_data()?.get_hand().get_dice_num();
                 let cursor_pos = self.state.get_mut_play_cursor_pos()?;
                 match cursor_pos {
-                    PlayCursorPos::Hand(pos) | PlayCursorPos::Dust(pos) => {
-                        if *pos > 0 {
-                            *pos -= 1;
-                        }
+                    PlayCursorPos::Hand(pos) | PlayCursorPos::Dust(pos) if *pos > 0 => {
+                        *pos -= 1;
                     }
                     PlayCursorPos::Table(..) => {
-                        *cursor_pos = PlayCursorPos::Hand(Hand::DICE_NUM - 1);
+                        let has_moved = self.left_action_in_score_table()?;
+                        if !has_moved {
+                            *self.state.get_mut_play_cursor_pos()? =
+                                PlayCursorPos::Hand(dice_num - 1);
+                        }
                     }
                     _ => (),
                 }
@@ -502,6 +1319,7 @@ impl App {
             }
 
             Actions::Right => {
+                let dice_num = self.state.get_play_data()?.get_hand().get_dice_num();
                 let cursor_pos = self.state.get_mut_play_cursor_pos()?;
                 match cursor_pos {
                     PlayCursorPos::Roll => {
@@ -509,17 +1327,55 @@ impl App {
                     }
                     PlayCursorPos::Hand(pos) | PlayCursorPos::Dust(pos) => {
                         let new_pos = *pos + 1;
-                        if new_pos < Hand::DICE_NUM {
+                        if new_pos < dice_num {
                             *pos = new_pos;
                         } else {
                             self.move_cursor_pos_to_table()?;
                         }
                     }
+                    PlayCursorPos::Table(..) => {
+                        self.right_action_in_score_table()?;
+                    }
                     _ => (),
                 }
                 AppReturn::Continue
             }
 
+            Actions::Hint => {
+                self.toggle_hints();
+                AppReturn::Continue
+            }
+
+            Actions::Odds => {
+                self.shows_odds = !self.shows_odds;
+                self.update_odds()?;
+                AppReturn::Continue
+            }
+
+            Actions::MoveLeft | Actions::MoveRight => {
+                let dice_num = self.state.get_play_data()?.get_hand().get_dice_num();
+                let (&PlayCursorPos::Hand(pos) | &PlayCursorPos::Dust(pos)) =
+                    self.state.get_play_cursor_pos()?
+                else {
+                    return Ok(AppReturn::Continue);
+                };
+                let other = match input_event.action() {
+                    Actions::MoveLeft if pos > 0 => pos - 1,
+                    Actions::MoveRight if pos + 1 < dice_num => pos + 1,
+                    _ => return Ok(AppReturn::Continue),
+                };
+                self.state
+                    .get_mut_play_data()?
+                    .get_mut_hand()
+                    .swap(pos, other)?;
+                if let PlayCursorPos::Hand(pos) | PlayCursorPos::Dust(pos) =
+                    self.state.get_mut_play_cursor_pos()?
+                {
+                    *pos = other;
+                }
+                AppReturn::Continue
+            }
+
             Actions::Up => {
                 match self.state.get_play_cursor_pos()? {
                     PlayCursorPos::Hand(..) => {
@@ -578,17 +1434,363 @@ impl App {
                 AppReturn::Continue
             }
 
+            Actions::Left => {
+                self.left_action_in_score_table()?;
+                AppReturn::Continue
+            }
+
+            Actions::Right => {
+                self.right_action_in_score_table()?;
+                AppReturn::Continue
+            }
+
+            Actions::Hint => {
+                self.toggle_hints();
+                AppReturn::Continue
+            }
+
+            Actions::Odds => {
+                self.shows_odds = !self.shows_odds;
+                self.update_odds()?;
+                AppReturn::Continue
+            }
+
             _ => AppReturn::Continue,
         })
     }
 
+    /* Later games reuse the solver, since solving the game again takes seconds */
+    fn next_bots(&mut self, seed: u64, solvable: bool) -> Bots {
+        match self.bots.take() {
+            Some(bots) => bots.for_next_game(seed, solvable),
+            None => Bots::new(seed, solvable),
+        }
+    }
+
+    fn toggle_hints(&mut self) {
+        self.shows_hints = !self.shows_hints;
+        if self.shows_hints && self.can_hint() {
+            if let Some(bots) = &mut self.bots {
+                bots.start_solving();
+            }
+        }
+    }
+
+    /* Keys may change the dice or the holds, so the hint is worked out again after them */
+    fn update_hint(&mut self, is_tick: bool) -> Result<()> {
+        let play = match self.state.get_play_data() {
+            Ok(play)
+                if matches!(
+                    play.get_phase(),
+                    PlayPhase::SelectOrReroll(..) | PlayPhase::Select
+                ) =>
+            {
+                play
+            }
+            _ => {
+                self.hint = None;
+                return Ok(());
+            }
+        };
+        if !self.shows_hints || !self.can_hint() || self.bot_to_move()?.is_some() {
+            self.hint = None;
+            return Ok(());
+        }
+        if !is_tick {
+            self.hint = None;
+        }
+        if self.hint.is_none() {
+            let game_data = self.game_data.as_ref().ok_or(AppError::NoGameData)?;
+            let table = game_data.get_score_table(play.get_player_id());
+            let bots = self.bots.as_mut().ok_or(AppError::NoGameData)?;
+            self.hint = bots.hint(table, play)?;
+        }
+        Ok(())
+    }
+
+    fn update_odds(&mut self) -> Result<()> {
+        let (true, Ok(play)) = (self.shows_odds, self.state.get_play_data()) else {
+            self.turn_odds.clear();
+            return Ok(());
+        };
+        /* The dice not held are still rolling in the Roll phase */
+        let remaining_rolls = match play.get_phase() {
+            PlayPhase::Roll(..) => play.get_remaining_rolls() + 1,
+            _ => play.get_remaining_rolls(),
+        };
+        if self.odds.is_none() {
+            let game_data = self.get_game_data()?;
+            let odds = Odds::new(game_data.get_rule_set(), game_data.get_settings().die)?;
+            self.odds = Some(odds);
+        }
+        let play = self.state.get_play_data()?;
+        let held = play.get_hand().get_held_dice()?;
+        self.turn_odds = self
+            .odds
+            .as_mut()
+            .and_then(|odds| odds.get(&held, remaining_rolls))
+            .unwrap_or_default();
+        Ok(())
+    }
+
+    /* The difficulty of the bot whose turn it is, unless the dice are rolling */
+    fn bot_to_move(&self) -> Result<Option<Difficulty>> {
+        let Ok(play) = self.state.get_play_data() else {
+            return Ok(None);
+        };
+        if matches!(play.get_phase(), PlayPhase::Roll(..)) {
+            return Ok(None);
+        }
+        Ok(match self.get_game_data()?.get_seat(play.get_player_id()) {
+            Seat::Bot(difficulty) => Some(difficulty),
+            Seat::Human => None,
+        })
+    }
+
+    /* Bots ignore the keys and make a move every MOVE_DELAY */
+    fn do_action_in_bot_turn(
+        &mut self,
+        difficulty: Difficulty,
+        input_event: InputEvent,
+    ) -> Result<AppReturn> {
+        if matches!(input_event.action(), Actions::Exit) {
+            return Ok(AppReturn::Exit);
+        }
+        if !matches!(input_event, InputEvent::Tick) {
+            return Ok(AppReturn::Continue);
+        }
+
+        self.bot_ticks += 1;
+        if self.bot_ticks * TICK_RATE < Bots::MOVE_DELAY {
+            return Ok(AppReturn::Continue);
+        }
+        self.bot_ticks = 0;
+        self.bot_move(difficulty)?;
+        Ok(AppReturn::Continue)
+    }
+
+    /* One step of the bot's move through the same actions as the keys of a human player */
+    fn bot_move(&mut self, difficulty: Difficulty) -> Result<()> {
+        let play = self.state.get_play_data()?;
+        if matches!(play.get_phase(), PlayPhase::Init) {
+            return self.start_rolling();
+        }
+        if self.bot_decision.is_none() {
+            let game_data = self.game_data.as_ref().ok_or(AppError::NoGameData)?;
+            let bots = self.bots.as_mut().ok_or(AppError::NoGameData)?;
+            self.bot_decision = bots.decide(difficulty, game_data, play)?;
+        }
+        let Some(decision) = &self.bot_decision else {
+            return Ok(());
+        };
+
+        let cursor = self.state.get_play_cursor_pos()?;
+        match decision {
+            Decision::Reroll(holds) => {
+                let hand = self.state.get_play_data()?.get_hand();
+                let mut toggle = None;
+                for (pos, &hold) in holds.iter().enumerate() {
+                    if hand.is_held(pos)? != hold {
+                        toggle = Some((pos, hold));
+                        break;
+                    }
+                }
+                match toggle {
+                    Some((pos, hold)) => {
+                        self.state
+                            .get_mut_play_data()?
+                            .get_mut_hand()
+                            .hold(pos, hold)?;
+                        *self.state.get_mut_play_cursor_pos()? = if hold {
+                            PlayCursorPos::Hand(pos)
+                        } else {
+                            PlayCursorPos::Dust(pos)
+                        };
+                    }
+                    None if *cursor != PlayCursorPos::Roll => {
+                        *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Roll;
+                    }
+                    None => {
+                        self.bot_decision = None;
+                        self.start_rolling()?;
+                    }
+                }
+            }
+            &Decision::Fill(col, b) => {
+                if *cursor != PlayCursorPos::Table(b, col) {
+                    *self.state.get_mut_play_cursor_pos()? = PlayCursorPos::Table(b, col);
+                } else {
+                    self.bot_decision = None;
+                    self.confirm_score_action()?;
+                }
+            }
+            &Decision::Announce(announcement) => {
+                let target = match announcement {
+                    Some((col, b)) => PlayCursorPos::Table(b, col),
+                    None => PlayCursorPos::Roll,
+                };
+                if *cursor != target {
+                    *self.state.get_mut_play_cursor_pos()? = target;
+                } else {
+                    self.bot_decision = None;
+                    self.announce_action()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn do_action_in_result(&mut self, input_event: InputEvent) -> Result<AppReturn> {
         Ok(match input_event.action() {
             Actions::Exit => AppReturn::Exit,
 
             Actions::Select => AppReturn::Exit,
 
+            Actions::Review if self.can_review() => {
+                self.state = AppState::Review(0);
+                AppReturn::Continue
+            }
+
             _ => AppReturn::Continue,
         })
     }
+
+    fn do_action_in_review(&mut self, input_event: InputEvent) -> Result<AppReturn> {
+        if self.review.is_none() {
+            let game_data = self.game_data.as_ref().ok_or(AppError::NoGameData)?;
+            let bots = self.bots.as_mut().ok_or(AppError::NoGameData)?;
+            self.review = bots.review(game_data)?;
+        }
+        let num_entries = self.review.as_ref().map_or(0, |r| r.get_entries().len());
+        let AppState::Review(top) = &mut self.state else {
+            panic!("Unexpected state")
+        };
+        Ok(match input_event.action() {
+            Actions::Exit => AppReturn::Exit,
+
+            Actions::Select | Actions::Review => {
+                self.state = AppState::Result;
+                AppReturn::Continue
+            }
+
+            Actions::Up => {
+                *top = top.saturating_sub(1);
+                AppReturn::Continue
+            }
+
+            Actions::Down => {
+                if *top + 1 < num_entries {
+                    *top += 1;
+                }
+                AppReturn::Continue
+            }
+
+            _ => AppReturn::Continue,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn enter() -> InputEvent {
+        InputEvent::Input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+    }
+
+    fn is_rolling(app: &App) -> bool {
+        matches!(
+            app.state.get_play_data().unwrap().get_phase(),
+            PlayPhase::Roll(..)
+        )
+    }
+
+    /* Starts the first roll of a solo game */
+    fn rolling_app(preferences: Preferences) -> App {
+        let mut app = App {
+            preferences,
+            ..App::new()
+        };
+        app.game_data = Some(GameData::new(
+            1,
+            Variant::Yahtzee.rule_set(),
+            GameSettings::default(),
+            0,
+        ));
+        app.state = AppState::initialized_play_state();
+        app.do_action(InputEvent::Tick).unwrap();
+        app.do_action(enter()).unwrap();
+        app
+    }
+
+    #[test]
+    fn tumble_dice_test() {
+        let preferences = Preferences {
+            roll_animation: Preferences::MIN_ROLL_ANIMATION,
+            ..Preferences::default()
+        };
+        let mut app = rolling_app(preferences);
+        let ticks = Preferences::MIN_ROLL_ANIMATION
+            .as_millis()
+            .div_ceil(TICK_RATE.as_millis());
+        for _ in 1..ticks {
+            app.do_action(InputEvent::Tick).unwrap();
+            assert!(is_rolling(&app));
+        }
+        app.do_action(InputEvent::Tick).unwrap();
+        assert!(!is_rolling(&app));
+
+        /* Enter skips the animation */
+        let mut app = rolling_app(Preferences::default());
+        app.do_action(InputEvent::Tick).unwrap();
+        assert!(is_rolling(&app));
+        app.do_action(enter()).unwrap();
+        assert!(!is_rolling(&app));
+    }
+
+    #[test]
+    fn toggle_odds_test() {
+        let preferences = Preferences {
+            reduces_motion: true,
+            ..Preferences::default()
+        };
+        let mut app = rolling_app(preferences);
+        assert!(app.shows_odds());
+        assert!(!app.get_turn_odds().is_empty());
+
+        let odds = || InputEvent::Input(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE));
+        app.do_action(odds()).unwrap();
+        assert!(!app.shows_odds());
+        assert!(app.get_turn_odds().is_empty());
+        app.do_action(odds()).unwrap();
+        assert!(!app.get_turn_odds().is_empty());
+    }
+
+    #[test]
+    fn reduced_motion_test() {
+        let preferences = Preferences {
+            reduces_motion: true,
+            ..Preferences::default()
+        };
+        let app = rolling_app(preferences);
+        assert!(!is_rolling(&app));
+
+        /* The daily challenge follows the preferences as well */
+        let preferences = Preferences {
+            arrangement: Arrangement::Sorted,
+            ..preferences
+        };
+        let mut app = App {
+            preferences,
+            state: AppState::StartMenu(StartMenuSelection::Daily),
+            ..App::new()
+        };
+        app.do_action(enter()).unwrap();
+        app.do_action(InputEvent::Tick).unwrap();
+        app.do_action(enter()).unwrap();
+        assert!(!is_rolling(&app));
+        let pips = app.state.get_play_data().unwrap().get_hand().get_pips();
+        assert!(pips.windows(2).all(|w| w[0] <= w[1]));
+    }
 }
@@ -0,0 +1,523 @@
+use crate::dice::{Dice, Pip};
+use crate::game_data::{GameData, GameSettings};
+use crate::hand::DieSpec;
+use crate::play::{Play, PlayPhase};
+use crate::review::Review;
+use crate::rule_set::{RuleSet, Variant};
+use crate::score_table::ScoreTable;
+use crate::scoring::Boxes;
+use crate::solver::{Solver, TurnState};
+use anyhow::{anyhow, ensure, Context, Result};
+use enum_iterator::Sequence;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fmt;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BotError {
+    #[error("No box can be filled with the dice")]
+    NoFillableBox,
+    #[error("The solver stopped before it finished")]
+    SolverPanicked,
+    #[error("The solver does not know the dice")]
+    UnknownDice,
+}
+
+#[derive(PartialEq, Eq, Sequence, Clone, Copy)]
+pub enum Difficulty {
+    /* Any legal move with the same chance */
+    Random,
+    /* Chases the most common pip and scores the most it can now */
+    Greedy,
+    /* The solver's strategy where it applies, otherwise Greedy */
+    NearOptimal,
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Difficulty::Random => f.pad("Random"),
+            Difficulty::Greedy => f.pad("Greedy"),
+            Difficulty::NearOptimal => f.pad("Near-optimal"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Sequence, Clone, Copy)]
+pub enum Seat {
+    Human,
+    Bot(Difficulty),
+}
+
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Seat::Human => f.pad("Human"),
+            Seat::Bot(difficulty) => f.pad(&format!("Bot ({})", difficulty)),
+        }
+    }
+}
+
+/* What a bot does next in its turn */
+#[derive(PartialEq, Eq)]
+pub enum Decision {
+    /* Holds the dice in the slots marked true and rolls the others */
+    Reroll(Vec<bool>),
+    Fill(usize, Boxes),
+    /* None skips the announcement */
+    Announce(Option<(usize, Boxes)>),
+}
+
+/* What the solver recommends in a turn, with the expected final scores of the choices */
+pub struct Hint {
+    /* The slots of the dice to hold, all of them when the dice should be scored now */
+    holds: Vec<bool>,
+    /* The recommended hold and the hold of the player while rolls are left */
+    hold_values: Option<(f64, f64)>,
+    boxes: Vec<(Boxes, f64)>,
+}
+
+impl Hint {
+    pub fn is_held(&self, pos: usize) -> bool {
+        self.holds.get(pos).copied().unwrap_or(false)
+    }
+
+    /* Whether the best move is to score the dice rather than to roll again */
+    pub fn scores_now(&self) -> bool {
+        self.holds.iter().all(|&h| h)
+    }
+
+    pub fn get_hold_values(&self) -> Option<(f64, f64)> {
+        self.hold_values
+    }
+
+    pub fn get_box_value(&self, b: Boxes) -> Option<f64> {
+        self.boxes.iter().find(|&&(x, ..)| x == b).map(|&(.., v)| v)
+    }
+
+    pub fn get_best_box(&self) -> Option<(Boxes, f64)> {
+        self.boxes
+            .iter()
+            .copied()
+            .reduce(|best, (b, value)| if value > best.1 { (b, value) } else { best })
+    }
+}
+
+enum SolverState {
+    Unused,
+    Solving(JoinHandle<Solver>),
+    Ready(Box<Solver>),
+}
+
+/* The computer players of a game, which share a random source and the solver */
+pub struct Bots {
+    rng: StdRng,
+    /* Whether the solver knows the rules of the game */
+    solvable: bool,
+    solver: SolverState,
+}
+
+impl Bots {
+    /* How long a bot waits before each move so that people can follow it */
+    pub const MOVE_DELAY: Duration = Duration::from_millis(500);
+
+    pub fn new(seed: u64, solvable: bool) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            solvable,
+            solver: SolverState::Unused,
+        }
+    }
+
+    /* The bots of the next game keep the solver, which only depends on the rules */
+    pub fn for_next_game(self, seed: u64, solvable: bool) -> Self {
+        Self {
+            solver: self.solver,
+            ..Self::new(seed, solvable)
+        }
+    }
+
+    /* The solver works out the whole game in the background */
+    pub fn start_solving(&mut self) {
+        if let (true, SolverState::Unused) = (self.solvable, &self.solver) {
+            self.solver = SolverState::Solving(thread::spawn(|| {
+                let mut solver = Solver::new();
+                solver.expected_score(TurnState::default());
+                solver
+            }));
+        }
+    }
+
+    pub fn is_solvable(&self) -> bool {
+        self.solvable
+    }
+
+    /* The solver only knows solitaire Yahtzee with the standard dice and rolls */
+    pub fn can_solve(variant: Variant, settings: &GameSettings) -> bool {
+        variant == Variant::Yahtzee
+            && settings.die == &DieSpec::D6
+            && settings.rolls_per_turn == Play::DEFAULT_ROLLS_PER_TURN
+            && !settings.carries_over_rolls
+    }
+
+    pub fn is_solving(&self) -> bool {
+        matches!(&self.solver, SolverState::Solving(handle) if !handle.is_finished())
+    }
+
+    fn solver(&mut self) -> Result<Option<&mut Solver>> {
+        if let SolverState::Solving(handle) = &self.solver {
+            if !handle.is_finished() {
+                return Ok(None);
+            }
+            let SolverState::Solving(handle) =
+                std::mem::replace(&mut self.solver, SolverState::Unused)
+            else {
+                unreachable!()
+            };
+            let solver = handle
+                .join()
+                .map_err(|_| anyhow!(BotError::SolverPanicked))?;
+            self.solver = SolverState::Ready(Box::new(solver));
+        }
+        Ok(match &mut self.solver {
+            SolverState::Ready(solver) => Some(solver),
+            _ => None,
+        })
+    }
+
+    /* None while the bot waits for the solver */
+    pub fn decide(
+        &mut self,
+        difficulty: Difficulty,
+        game_data: &GameData,
+        play: &Play,
+    ) -> Result<Option<Decision>> {
+        let rule = game_data.get_rule_set();
+        let table = game_data.get_score_table(play.get_player_id());
+        Ok(Some(match (difficulty, play.get_phase()) {
+            (Difficulty::Random, PlayPhase::Announce) => self.random_announcement(rule, table),
+            (.., PlayPhase::Announce) => greedy_announcement(rule, table, play)?,
+            (Difficulty::Random, ..) => self.random_move(rule, table, play)?,
+            (Difficulty::Greedy, ..) => greedy_move(rule, table, play)?,
+            (Difficulty::NearOptimal, ..) => match self.solver {
+                SolverState::Unused => greedy_move(rule, table, play)?,
+                _ => match self.solver()? {
+                    Some(solver) => optimal_move(solver, table, play)?,
+                    None => return Ok(None),
+                },
+            },
+        }))
+    }
+
+    /* The solver's advice for the player's dice, None while it is still solving */
+    pub fn hint(&mut self, table: &ScoreTable, play: &Play) -> Result<Option<Hint>> {
+        let Some(solver) = self.solver()? else {
+            return Ok(None);
+        };
+        let column = table.get_column(0);
+        let state = TurnState::from_column(column);
+        let total = f64::from(column.get_total_score());
+        let hand = play.get_hand();
+        let pips = hand.get_pips();
+        let dice = hand.get_dice()?;
+        let rerolls = play.get_remaining_rolls();
+
+        let (holds, hold_values) = if rerolls > 0 {
+            let (hold, best) = solver.best_hold(state, &dice, rerolls);
+            let current = solver
+                .hold_value(state, &hand.get_held_dice()?, rerolls)
+                .context(BotError::UnknownDice)?;
+            (
+                hold_mask(&pips, &hold),
+                Some((total + best, total + current)),
+            )
+        } else {
+            (vec![true; pips.len()], None)
+        };
+        let boxes = solver
+            .box_values(state, &dice)
+            .into_iter()
+            .map(|(b, value)| (b, total + value))
+            .collect();
+        Ok(Some(Hint {
+            holds,
+            hold_values,
+            boxes,
+        }))
+    }
+
+    /* None while the solver is still solving */
+    pub fn review(&mut self, game_data: &GameData) -> Result<Option<Review>> {
+        match self.solver()? {
+            Some(solver) => Review::new(solver, game_data).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn random_announcement(&mut self, rule: &dyn RuleSet, table: &ScoreTable) -> Decision {
+        let mut choices: Vec<_> = announceable_boxes(rule, table)
+            .into_iter()
+            .map(Some)
+            .collect();
+        if !table.must_announce() {
+            choices.push(None);
+        }
+        Decision::Announce(choices[self.rng.gen_range(0..choices.len())])
+    }
+
+    fn random_move(
+        &mut self,
+        rule: &dyn RuleSet,
+        table: &ScoreTable,
+        play: &Play,
+    ) -> Result<Decision> {
+        let fillable = fillable_boxes(rule, table, &play.get_hand().get_dice()?);
+        let dice_num = play.get_hand().get_dice_num();
+        /* Every way to hold the dice but holding all of them rerolls */
+        let rerolls = match play.get_phase() {
+            PlayPhase::SelectOrReroll(..) => (1 << dice_num) - 1,
+            _ => 0,
+        };
+        ensure!(!fillable.is_empty(), BotError::NoFillableBox);
+        let choice = self.rng.gen_range(0..rerolls + fillable.len());
+        if choice < rerolls {
+            return Ok(Decision::Reroll(
+                (0..dice_num).map(|i| choice & 1 << i != 0).collect(),
+            ));
+        }
+        let (col, b) = fillable[choice - rerolls];
+        Ok(Decision::Fill(col, b))
+    }
+}
+
+fn announceable_boxes(rule: &dyn RuleSet, table: &ScoreTable) -> Vec<(usize, Boxes)> {
+    (0..table.get_num_columns())
+        .flat_map(|col| rule.boxes().iter().map(move |&b| (col, b)))
+        .filter(|&(col, b)| table.can_announce(col, b))
+        .collect()
+}
+
+fn fillable_boxes(rule: &dyn RuleSet, table: &ScoreTable, dice: &Dice) -> Vec<(usize, Boxes)> {
+    (0..table.get_num_columns())
+        .flat_map(|col| rule.boxes().iter().map(move |&b| (col, b)))
+        .filter(|&(col, b)| table.can_fill(col, b, dice))
+        .collect()
+}
+
+/* The box which adds the most to the total now */
+fn greedy_box(rule: &dyn RuleSet, table: &ScoreTable, play: &Play) -> Result<Decision> {
+    let dice = play.get_hand().get_dice()?;
+    let turn = play.get_turn_context();
+    let (col, b) = fillable_boxes(rule, table, &dice)
+        .into_iter()
+        .max_by_key(|&(col, b)| table.get_total_score_if_filled_with(col, b, &dice, &turn))
+        .context(BotError::NoFillableBox)?;
+    Ok(Decision::Fill(col, b))
+}
+
+/* Scores a pattern which every die makes up at once, or else chases the most common face */
+fn greedy_move(rule: &dyn RuleSet, table: &ScoreTable, play: &Play) -> Result<Decision> {
+    let pips = play.get_hand().get_pips();
+    let dice = play.get_hand().get_dice()?;
+    let most_common = dice.groups().max_by_key(|&(p, c)| (c, p));
+    match (play.get_phase(), most_common) {
+        (PlayPhase::SelectOrReroll(..), Some((pip, count))) if count < pips.len() => {
+            match greedy_box(rule, table, play)? {
+                Decision::Fill(col, b) if is_made(rule, b, &dice) => Ok(Decision::Fill(col, b)),
+                _ => Ok(Decision::Reroll(pips.iter().map(|&p| p == pip).collect())),
+            }
+        }
+        _ => greedy_box(rule, table, play),
+    }
+}
+
+/* Whether the box scores only with every one of the dice, as a straight or a full house does */
+fn is_made(rule: &dyn RuleSet, b: Boxes, dice: &Dice) -> bool {
+    rule.look_up_score(b, dice) > 0
+        && dice
+            .groups()
+            .all(|(pip, ..)| rule.look_up_score(b, &dice.without_one(pip)) == 0)
+}
+
+/* Announces the box which scores the most with the first roll if an announcement is required */
+fn greedy_announcement(rule: &dyn RuleSet, table: &ScoreTable, play: &Play) -> Result<Decision> {
+    if !table.must_announce() {
+        return Ok(Decision::Announce(None));
+    }
+    let dice = play.get_hand().get_dice()?;
+    let turn = play.get_turn_context();
+    let best = announceable_boxes(rule, table)
+        .into_iter()
+        .max_by_key(|&(col, b)| table.get_column(col).score_of(b, &dice, &turn));
+    Ok(Decision::Announce(best))
+}
+
+fn optimal_move(solver: &mut Solver, table: &ScoreTable, play: &Play) -> Result<Decision> {
+    let state = TurnState::from_column(table.get_column(0));
+    let dice = play.get_hand().get_dice()?;
+    let rerolls = play.get_remaining_rolls();
+    if rerolls > 0 {
+        let (hold, ..) = solver.best_hold(state, &dice, rerolls);
+        if hold != dice {
+            return Ok(Decision::Reroll(hold_mask(
+                &play.get_hand().get_pips(),
+                &hold,
+            )));
+        }
+    }
+    let (b, ..) = solver
+        .best_box(state, &dice)
+        .context(BotError::NoFillableBox)?;
+    Ok(Decision::Fill(0, b))
+}
+
+/* The slots to hold for the dice to keep, from the left for the same pips */
+fn hold_mask(pips: &[Pip], hold: &Dice) -> Vec<bool> {
+    let mut rest: Vec<_> = hold.iter().collect();
+    pips.iter()
+        .map(|p| match rest.iter().position(|q| q == p) {
+            Some(i) => {
+                rest.swap_remove(i);
+                true
+            }
+            None => false,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bot::*;
+
+    #[test]
+    fn hold_mask_test() {
+        let pips: Vec<_> = [3, 5, 3, 1, 3]
+            .into_iter()
+            .map(|p| Pip::new(p).unwrap())
+            .collect();
+        let hold = Dice::try_from([3, 3, 5]).unwrap();
+        assert_eq!(
+            hold_mask(&pips, &hold),
+            vec![true, true, true, false, false]
+        );
+        assert_eq!(hold_mask(&pips, &Dice::default()), vec![false; 5]);
+    }
+
+    fn new_game() -> GameData {
+        GameData::new(1, Variant::Yahtzee.rule_set(), GameSettings::default(), 0)
+    }
+
+    #[test]
+    fn greedy_test() {
+        let game_data = new_game();
+        let mut bots = Bots::new(0, false);
+        let mut decide = |dice, rerolls| {
+            let play = Play::rolled(dice, rerolls);
+            bots.decide(Difficulty::Greedy, &game_data, &play)
+                .unwrap()
+                .unwrap()
+        };
+        assert!(
+            decide(&[2, 5, 2, 6, 2], 2) == Decision::Reroll(vec![true, false, true, false, true])
+        );
+        assert!(decide(&[4, 4, 4, 4, 4], 2) == Decision::Fill(0, Boxes::Yahtzee));
+        assert!(decide(&[1, 2, 3, 4, 5], 0) == Decision::Fill(0, Boxes::LargeStraight));
+        assert!(decide(&[1, 2, 3, 4, 5], 2) == Decision::Fill(0, Boxes::LargeStraight));
+        assert!(decide(&[2, 2, 2, 5, 5], 1) == Decision::Fill(0, Boxes::FullHouse));
+        assert!(
+            decide(&[1, 2, 3, 4, 6], 1) == Decision::Reroll(vec![false, false, false, false, true])
+        );
+    }
+
+    #[test]
+    fn random_test() {
+        let game_data = new_game();
+        let table = game_data.get_score_table(0);
+        let mut bots = Bots::new(0, false);
+        for _ in 0..50 {
+            let play = Play::rolled(&[1, 1, 2, 3, 6], 0);
+            let Some(Decision::Fill(col, b)) =
+                bots.decide(Difficulty::Random, &game_data, &play).unwrap()
+            else {
+                panic!("a bot must score without rolls left");
+            };
+            assert!(table.can_fill(col, b, &play.get_hand().get_dice().unwrap()));
+
+            let play = Play::rolled(&[1, 1, 2, 3, 6], 1);
+            match bots.decide(Difficulty::Random, &game_data, &play).unwrap() {
+                Some(Decision::Reroll(holds)) => assert!(holds.contains(&false)),
+                Some(Decision::Fill(..)) => (),
+                _ => panic!("unexpected decision"),
+            }
+        }
+    }
+
+    fn large_straight_left() -> GameData {
+        let mut game_data = new_game();
+        *game_data.get_mut_score_table(0) = ScoreTable::with_only_left(&[Boxes::LargeStraight]);
+        game_data
+    }
+
+    fn solved_bots() -> Bots {
+        Bots {
+            rng: StdRng::seed_from_u64(0),
+            solvable: true,
+            solver: SolverState::Ready(Box::default()),
+        }
+    }
+
+    #[test]
+    fn optimal_test() {
+        let game_data = large_straight_left();
+        let mut bots = solved_bots();
+        let mut decide = |dice, rerolls| {
+            let play = Play::rolled(dice, rerolls);
+            bots.decide(Difficulty::NearOptimal, &game_data, &play)
+                .unwrap()
+                .unwrap()
+        };
+        assert!(
+            decide(&[2, 3, 4, 5, 5], 2) == Decision::Reroll(vec![true, true, true, true, false])
+        );
+        assert!(decide(&[2, 3, 4, 5, 6], 2) == Decision::Fill(0, Boxes::LargeStraight));
+    }
+
+    #[test]
+    fn next_game_test() {
+        let bots = solved_bots().for_next_game(1, false);
+        assert!(matches!(bots.solver, SolverState::Ready(..)));
+        assert!(!bots.is_solvable());
+        let mut bots = Bots::new(0, false).for_next_game(1, true);
+        assert!(matches!(bots.solver, SolverState::Unused));
+        bots.start_solving();
+        assert!(matches!(bots.solver, SolverState::Solving(..)));
+    }
+
+    #[test]
+    fn hint_test() {
+        let game_data = large_straight_left();
+        let table = game_data.get_score_table(0);
+        let total = f64::from(table.get_total_score());
+        let mut bots = solved_bots();
+
+        let play = Play::rolled(&[2, 3, 4, 5, 5], 2);
+        let hint = bots.hint(table, &play).unwrap().unwrap();
+        assert!(!hint.scores_now());
+        assert!((0..4).all(|pos| hint.is_held(pos)));
+        assert!(!hint.is_held(4));
+        /* The player holds every die after the roll, which scores 0 now */
+        let (best, yours) = hint.get_hold_values().unwrap();
+        assert!(best > yours);
+        assert_eq!(yours, total);
+        assert!(hint.get_best_box() == Some((Boxes::LargeStraight, total)));
+
+        let play = Play::rolled(&[2, 3, 4, 5, 6], 0);
+        let hint = bots.hint(table, &play).unwrap().unwrap();
+        assert!(hint.scores_now());
+        assert!(hint.get_hold_values().is_none());
+        assert_eq!(hint.get_box_value(Boxes::LargeStraight), Some(total + 40.0));
+        assert_eq!(hint.get_box_value(Boxes::Chance), None);
+    }
+}
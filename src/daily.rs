@@ -0,0 +1,202 @@
+use crate::game_data::split_mix;
+use anyhow::{Context, Result};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Date {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /* The daily challenge changes at midnight UTC so that everyone shares the same day */
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::from_days_since_epoch(secs / (24 * 60 * 60))
+    }
+
+    fn from_days_since_epoch(days: u64) -> Self {
+        /* Converts days since 1970-01-01 into a date of the proleptic Gregorian calendar */
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + u64::from(month <= 2);
+
+        Self {
+            year: year as u32,
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+
+    /* Mixed so that the seeds of nearby days have nothing in common */
+    pub fn seed(&self) -> u64 {
+        split_mix(u64::from(self.year) * 10_000 + u64::from(self.month) * 100 + u64::from(self.day))
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&format!(
+            "{:04}-{:02}-{:02}",
+            self.year, self.month, self.day
+        ))
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Attempt {
+    Recorded,
+    Practice,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Attempt::Recorded => f.pad("Recorded"),
+            Attempt::Practice => f.pad("Practice"),
+        }
+    }
+}
+
+pub struct DailyChallenge {
+    date: Date,
+    attempt: Option<Attempt>,
+}
+
+impl DailyChallenge {
+    pub fn new(date: Date) -> Self {
+        Self {
+            date,
+            attempt: None,
+        }
+    }
+
+    pub fn get_date(&self) -> Date {
+        self.date
+    }
+
+    /* None until the game is finished */
+    pub fn get_attempt(&self) -> Option<Attempt> {
+        self.attempt
+    }
+
+    /* Only the first completed attempt of the day is recorded, later ones are practice */
+    pub fn finish(&mut self, log: &DailyLog, score: u32) -> Result<Attempt> {
+        if let Some(attempt) = self.attempt {
+            return Ok(attempt);
+        }
+
+        let attempt = if log.get_score(self.date)?.is_some() {
+            Attempt::Practice
+        } else {
+            log.record(self.date, score)?;
+            Attempt::Recorded
+        };
+        self.attempt = Some(attempt);
+        Ok(attempt)
+    }
+}
+
+/* One line per day in the form of "YYYY-MM-DD score" */
+pub struct DailyLog {
+    path: PathBuf,
+}
+
+impl Default for DailyLog {
+    fn default() -> Self {
+        let dir = std::env::var_os("HOME").map_or_else(PathBuf::new, PathBuf::from);
+        Self::new(dir.join(".yahtzee_daily"))
+    }
+}
+
+impl DailyLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn get_score(&self, date: Date) -> Result<Option<u32>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| format!("could not read {}", self.path.display()))
+            }
+        };
+        let date = date.to_string();
+        Ok(text.lines().find_map(|line| {
+            let (d, score) = line.split_once(' ')?;
+            (d == date).then(|| score.trim().parse().ok()).flatten()
+        }))
+    }
+
+    fn record(&self, date: Date, score: u32) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("could not open {}", self.path.display()))?;
+        writeln!(file, "{} {}", date, score)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::daily::*;
+
+    #[test]
+    fn date_test() {
+        let date = |year, month, day| Date { year, month, day };
+        assert_eq!(Date::from_days_since_epoch(0), date(1970, 1, 1));
+        assert_eq!(Date::from_days_since_epoch(11_016), date(2000, 2, 29));
+        assert_eq!(Date::from_days_since_epoch(20_743), date(2026, 10, 17));
+        assert_eq!(format!("{}", date(2026, 1, 5)), "2026-01-05");
+        let seed = date(2026, 1, 5).seed();
+        assert_ne!(seed, date(2026, 1, 6).seed());
+        assert!(seed.abs_diff(date(2026, 1, 6).seed()) > 1 << 32);
+    }
+
+    #[test]
+    fn daily_log_test() {
+        let path = std::env::temp_dir().join(format!("yahtzee_daily_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let log = DailyLog::new(path.clone());
+        let today = Date {
+            year: 2026,
+            month: 10,
+            day: 17,
+        };
+
+        let mut first = DailyChallenge::new(today);
+        assert_eq!(first.get_attempt(), None);
+        assert_eq!(first.finish(&log, 200).unwrap(), Attempt::Recorded);
+        assert_eq!(first.finish(&log, 200).unwrap(), Attempt::Recorded);
+
+        let mut second = DailyChallenge::new(today);
+        assert_eq!(second.finish(&log, 300).unwrap(), Attempt::Practice);
+        assert_eq!(log.get_score(today).unwrap(), Some(200));
+
+        let tomorrow = Date { day: 18, ..today };
+        assert_eq!(log.get_score(tomorrow).unwrap(), None);
+        assert_eq!(
+            DailyChallenge::new(tomorrow).finish(&log, 100).unwrap(),
+            Attempt::Recorded
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
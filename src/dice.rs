@@ -0,0 +1,187 @@
+use crate::hand::DieSpec;
+use anyhow::{ensure, Result};
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DiceError {
+    #[error("{0} is not a pip of a die")]
+    InvalidPip(u32),
+    #[error("The number of dice must be {0} or less")]
+    TooManyDice(usize),
+}
+
+/* The value of a face of a die */
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct Pip(u32);
+
+impl Pip {
+    pub const MIN: u32 = 1;
+    pub const MAX: u32 = DieSpec::MAX_VALUE;
+
+    pub fn new(value: u32) -> Result<Self> {
+        ensure!(
+            (Self::MIN..=Self::MAX).contains(&value),
+            DiceError::InvalidPip(value)
+        );
+        Ok(Self(value))
+    }
+
+    pub fn value(self) -> u32 {
+        self.0
+    }
+
+    pub fn all() -> impl DoubleEndedIterator<Item = Self> {
+        (Self::MIN..=Self::MAX).map(Self)
+    }
+
+    fn index(self) -> usize {
+        (self.0 - Self::MIN) as usize
+    }
+}
+
+impl fmt::Display for Pip {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/* Dice without their order, which is all that matters to score them */
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub struct Dice {
+    counts: [u8; Pip::MAX as usize],
+}
+
+impl Dice {
+    /* Enough for the six dice of Maxi Yatzy */
+    pub const MAX_LEN: usize = 6;
+
+    pub fn new<I: IntoIterator<Item = Pip>>(pips: I) -> Result<Self> {
+        let mut dice = Self::default();
+        for pip in pips {
+            dice.push(pip)?;
+        }
+        Ok(dice)
+    }
+
+    /* Every distinct hand of `num` dice showing the given pips */
+    pub fn multisets(num: usize, pips: &[Pip]) -> Vec<Self> {
+        match pips {
+            [] if num == 0 => vec![Self::default()],
+            [] => vec![],
+            [pip, rest @ ..] => (0..=num)
+                .flat_map(|count| {
+                    Self::multisets(num - count, rest)
+                        .into_iter()
+                        .map(move |mut dice| {
+                            dice.counts[pip.index()] = count as u8;
+                            dice
+                        })
+                })
+                .collect(),
+        }
+    }
+
+    pub fn push(&mut self, pip: Pip) -> Result<()> {
+        ensure!(
+            self.len() < Self::MAX_LEN,
+            DiceError::TooManyDice(Self::MAX_LEN)
+        );
+        self.counts[pip.index()] += 1;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts.iter().map(|&c| c as usize).sum()
+    }
+
+    pub fn count(&self, pip: Pip) -> usize {
+        self.counts[pip.index()] as usize
+    }
+
+    /* Distinct pips with the number of dice showing them, lowest first */
+    pub fn groups(&self) -> impl DoubleEndedIterator<Item = (Pip, usize)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(.., &c)| c > 0)
+            .map(|(i, &c)| (Pip(i as u32 + Pip::MIN), c as usize))
+    }
+
+    /* Every die, lowest first */
+    pub fn iter(&self) -> impl Iterator<Item = Pip> + '_ {
+        self.groups()
+            .flat_map(|(pip, count)| std::iter::repeat_n(pip, count))
+    }
+
+    pub fn sum(&self) -> u32 {
+        self.iter().map(|p| p.value()).sum()
+    }
+
+    /* The dice left after removing all the dice showing the pip */
+    pub fn without(&self, pip: Pip) -> Self {
+        let mut dice = *self;
+        dice.counts[pip.index()] = 0;
+        dice
+    }
+
+    /* The dice left after removing a die showing the pip */
+    pub fn without_one(&self, pip: Pip) -> Self {
+        let mut dice = *self;
+        dice.counts[pip.index()] = dice.counts[pip.index()].saturating_sub(1);
+        dice
+    }
+}
+
+impl<const N: usize> TryFrom<[u32; N]> for Dice {
+    type Error = anyhow::Error;
+
+    fn try_from(values: [u32; N]) -> Result<Self> {
+        let pips = values
+            .into_iter()
+            .map(Pip::new)
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(pips)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dice::*;
+
+    #[test]
+    fn pip_test() {
+        assert!(Pip::new(0).is_err());
+        assert_eq!(Pip::new(Pip::MAX).unwrap().value(), Pip::MAX);
+        assert!(Pip::new(Pip::MAX + 1).is_err());
+        assert!(Pip::new(3).unwrap() < Pip::new(4).unwrap());
+    }
+
+    #[test]
+    fn dice_test() {
+        let dice = Dice::try_from([5, 2, 5, 1, 5]).unwrap();
+        assert_eq!(dice, Dice::try_from([1, 2, 5, 5, 5]).unwrap());
+        assert_eq!(dice.len(), 5);
+        assert_eq!(dice.sum(), 18);
+        assert_eq!(dice.count(Pip::new(5).unwrap()), 3);
+        assert_eq!(
+            dice.iter().map(|p| p.value()).collect::<Vec<_>>(),
+            vec![1, 2, 5, 5, 5]
+        );
+        let without = dice.without(Pip::new(5).unwrap());
+        assert_eq!(without, Dice::try_from([2, 1]).unwrap());
+        let without_one = dice.without_one(Pip::new(5).unwrap());
+        assert_eq!(without_one, Dice::try_from([5, 2, 5, 1]).unwrap());
+        assert_eq!(without.without_one(Pip::new(5).unwrap()), without);
+        assert_eq!(Dice::default().len(), 0);
+
+        let d6: Vec<_> = Pip::all().take(6).collect();
+        assert_eq!(Dice::multisets(5, &d6).len(), 252);
+        assert_eq!(Dice::multisets(6, &d6).len(), 462);
+        assert!(Dice::multisets(5, &d6).iter().all(|d| d.len() == 5));
+
+        assert!(Dice::try_from([1, 9]).is_err());
+        assert!(Dice::try_from([1; Dice::MAX_LEN]).is_ok());
+        assert!(Dice::try_from([1; Dice::MAX_LEN + 1]).is_err());
+    }
+}
@@ -10,12 +10,22 @@ use std::{
     time::Duration,
 };
 
+/* How often a tick comes while no key is pressed */
+pub const TICK_RATE: Duration = Duration::from_millis(30);
+
 pub enum Actions {
     Select,
     Up,
     Down,
     Right,
     Left,
+    Number(u32),
+    Erase,
+    Hint,
+    Odds,
+    Review,
+    MoveLeft,
+    MoveRight,
     Exit,
     Pass,
 }
@@ -34,6 +44,26 @@ impl InputEvent {
                 ..
             }) => Actions::Select,
 
+            InputEvent::Input(KeyEvent {
+                code: KeyCode::Left,
+                modifiers: KeyModifiers::SHIFT,
+                ..
+            })
+            | InputEvent::Input(KeyEvent {
+                code: KeyCode::Char('<' | ','),
+                ..
+            }) => Actions::MoveLeft,
+
+            InputEvent::Input(KeyEvent {
+                code: KeyCode::Right,
+                modifiers: KeyModifiers::SHIFT,
+                ..
+            })
+            | InputEvent::Input(KeyEvent {
+                code: KeyCode::Char('>' | '.'),
+                ..
+            }) => Actions::MoveRight,
+
             InputEvent::Input(KeyEvent {
                 code: KeyCode::Up | KeyCode::Char('w'),
                 ..
@@ -54,6 +84,31 @@ impl InputEvent {
                 ..
             }) => Actions::Right,
 
+            InputEvent::Input(KeyEvent {
+                code: KeyCode::Char(c @ '0'..='9'),
+                ..
+            }) => Actions::Number(c.to_digit(10).unwrap()),
+
+            InputEvent::Input(KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            }) => Actions::Erase,
+
+            InputEvent::Input(KeyEvent {
+                code: KeyCode::Char('h'),
+                ..
+            }) => Actions::Hint,
+
+            InputEvent::Input(KeyEvent {
+                code: KeyCode::Char('o'),
+                ..
+            }) => Actions::Odds,
+
+            InputEvent::Input(KeyEvent {
+                code: KeyCode::Char('r'),
+                ..
+            }) => Actions::Review,
+
             InputEvent::Input(KeyEvent {
                 code: KeyCode::Char('c'),
                 modifiers: KeyModifiers::CONTROL,
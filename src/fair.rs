@@ -0,0 +1,584 @@
+use crate::dice::{Dice, Pip};
+use crate::hand::{DiceSource, DieSpec, Hand};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use thiserror::Error;
+
+pub type Hash = [u8; 32];
+
+fn sha256(parts: &[&[u8]]) -> Hash {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+fn to_hex(hash: &Hash) -> String {
+    hash.iter().fold(String::new(), |mut s, b| {
+        let _ = write!(s, "{:02x}", b);
+        s
+    })
+}
+
+fn from_hex(text: &str) -> Result<Hash> {
+    ensure!(text.len() == 64, FairDiceError::BrokenTranscript);
+    let mut hash = [0; 32];
+    for (i, b) in hash.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&text[(2 * i)..(2 * i + 2)], 16)?;
+    }
+    Ok(hash)
+}
+
+#[derive(Debug, Error)]
+pub enum FairDiceError {
+    #[error("{0} commitments are expected")]
+    WrongNumberOfCommitments(usize),
+    #[error("{0} reveals are expected")]
+    WrongNumberOfReveals(usize),
+    #[error("Contributions must be committed before they are revealed")]
+    NotCommitted,
+    #[error("Contributions must be revealed before the dice are rolled")]
+    NotRevealed,
+    #[error("The reveal of participant {0} does not match the commitment")]
+    RevealMismatch(usize),
+    #[error("Roll {0} does not match its contributions")]
+    RollMismatch(usize),
+    #[error("The transcript is broken")]
+    BrokenTranscript,
+    #[error("The exchange holds a commitment of participant {0} which was not posted here")]
+    ForeignCommitment(usize),
+    #[error("Roll {1} of the transcript does not have the reveal of participant {0}")]
+    MissingReveal(usize, usize),
+}
+
+/* A secret random value which a participant commits to before a roll */
+pub struct Contribution {
+    secret: Hash,
+}
+
+impl Contribution {
+    pub fn new<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self { secret: rng.gen() }
+    }
+
+    pub fn commitment(&self) -> Hash {
+        sha256(&[&self.secret])
+    }
+
+    pub fn reveal(&self) -> Hash {
+        self.secret
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RollRecord {
+    pub commitments: Vec<Hash>,
+    pub reveals: Vec<Hash>,
+    pub pips: Vec<Pip>,
+}
+
+impl RollRecord {
+    /* Nobody can steer the seed unless all of the other reveals are known in advance */
+    fn seed(&self) -> Hash {
+        let parts: Vec<&[u8]> = self.reveals.iter().map(|r| r.as_slice()).collect();
+        sha256(&parts)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Post {
+    Commitment,
+    Reveal,
+}
+
+/*
+ * A directory which every participant can read and write, such as a shared folder, where each
+ * one posts a commitment and then a reveal for every roll. A new directory is used for each game.
+ */
+#[derive(Clone)]
+pub struct Exchange {
+    dir: PathBuf,
+    participant: usize,
+    num_participants: usize,
+}
+
+impl Exchange {
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+    pub fn new(dir: PathBuf, participant: usize, num_participants: usize) -> Self {
+        Self {
+            dir,
+            participant,
+            num_participants,
+        }
+    }
+
+    pub fn get_dir(&self) -> &PathBuf {
+        &self.dir
+    }
+
+    pub fn get_num_participants(&self) -> usize {
+        self.num_participants
+    }
+
+    /* Where the host saves the transcript once the game is over */
+    pub fn transcript_file(&self) -> TranscriptFile {
+        TranscriptFile::new(self.dir.join("transcript"))
+    }
+
+    fn path(&self, round: usize, post: Post, participant: usize) -> PathBuf {
+        let name = match post {
+            Post::Commitment => "commitment",
+            Post::Reveal => "reveal",
+        };
+        self.dir.join(format!("{}-{}-{}", round, name, participant))
+    }
+
+    fn post(&self, round: usize, post: Post, hash: &Hash) -> Result<()> {
+        /* Renamed into place so that nobody reads half of it */
+        let path = self.path(round, post, self.participant);
+        let temporary = path.with_extension("tmp");
+        fs::write(&temporary, to_hex(hash))
+            .with_context(|| format!("could not write {}", temporary.display()))?;
+        fs::rename(&temporary, &path).with_context(|| format!("could not write {}", path.display()))
+    }
+
+    /* Every participant's post for the round, None until all of them are there */
+    fn collect(&self, round: usize, post: Post) -> Result<Option<Vec<Hash>>> {
+        let mut hashes = vec![];
+        for participant in 0..self.num_participants {
+            let path = self.path(round, post, participant);
+            match fs::read_to_string(&path) {
+                Ok(text) => hashes.push(from_hex(text.trim())?),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => {
+                    return Err(e).with_context(|| format!("could not read {}", path.display()))
+                }
+            }
+        }
+        Ok(Some(hashes))
+    }
+
+    /*
+     * Takes part in every roll of a game played elsewhere until its transcript is saved, then
+     * checks that each roll was seeded with this participant's reveal. Returns the number of rolls.
+     */
+    pub fn contribute<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<usize> {
+        let transcript_file = self.transcript_file();
+        /* The reveals posted so far, which the host may use as soon as they are there */
+        let mut reveals = vec![];
+        let mut own: Option<Contribution> = None;
+        let mut round = 0;
+        while !transcript_file.get_path().exists() {
+            match &own {
+                None => {
+                    let contribution = Contribution::new(rng);
+                    self.post(round, Post::Commitment, &contribution.commitment())?;
+                    own = Some(contribution);
+                }
+                Some(contribution) if reveals.len() == round => {
+                    if self.collect(round, Post::Commitment)?.is_some() {
+                        self.post(round, Post::Reveal, &contribution.reveal())?;
+                        reveals.push(contribution.reveal());
+                    }
+                }
+                Some(..) => {
+                    if self.collect(round, Post::Reveal)?.is_some() {
+                        own = None;
+                        round += 1;
+                    }
+                }
+            }
+            thread::sleep(Self::POLL_INTERVAL);
+        }
+
+        let (die, transcript) = transcript_file.load()?;
+        verify(die, &transcript)?;
+        for (index, record) in transcript.iter().enumerate() {
+            ensure!(
+                reveals.get(index) == record.reveals.get(self.participant),
+                FairDiceError::MissingReveal(self.participant, index)
+            );
+        }
+        Ok(transcript.len())
+    }
+}
+
+/* Dice whose every roll is seeded by the revealed contributions of all participants */
+pub struct FairDice {
+    num_participants: usize,
+    transcript: Vec<RollRecord>,
+    rng: Option<StdRng>,
+    /* Where the other participants post their contributions, None if all of them are here */
+    exchange: Option<Exchange>,
+    /* The number of rolls exchanged before this transcript started */
+    first_round: usize,
+    /* This participant's contribution while the roll is being exchanged */
+    own: Option<Contribution>,
+}
+
+impl FairDice {
+    pub fn new(num_participants: usize) -> Self {
+        Self {
+            num_participants,
+            transcript: vec![],
+            rng: None,
+            exchange: None,
+            first_round: 0,
+            own: None,
+        }
+    }
+
+    pub fn with_exchange(exchange: Exchange, first_round: usize) -> Self {
+        Self {
+            exchange: Some(exchange.clone()),
+            first_round,
+            ..Self::new(exchange.num_participants)
+        }
+    }
+
+    pub fn commit(&mut self, commitments: Vec<Hash>) -> Result<()> {
+        ensure!(
+            commitments.len() == self.num_participants,
+            FairDiceError::WrongNumberOfCommitments(self.num_participants)
+        );
+        self.transcript.push(RollRecord {
+            commitments,
+            reveals: vec![],
+            pips: vec![],
+        });
+        self.rng = None;
+        Ok(())
+    }
+
+    pub fn reveal(&mut self, reveals: Vec<Hash>) -> Result<()> {
+        let record = self
+            .transcript
+            .last_mut()
+            .filter(|r| r.reveals.is_empty())
+            .ok_or_else(|| anyhow!(FairDiceError::NotCommitted))?;
+        ensure!(
+            reveals.len() == self.num_participants,
+            FairDiceError::WrongNumberOfReveals(self.num_participants)
+        );
+        for (i, (reveal, commitment)) in reveals.iter().zip(&record.commitments).enumerate() {
+            ensure!(
+                sha256(&[reveal]) == *commitment,
+                FairDiceError::RevealMismatch(i)
+            );
+        }
+
+        record.reveals = reveals;
+        self.rng = Some(StdRng::from_seed(record.seed()));
+        Ok(())
+    }
+
+    /*
+     * Runs a whole round for participants who all play on this machine. The transcript then only
+     * shows that the rolls follow from the recorded reveals, since one party made all of them.
+     */
+    pub fn commit_and_reveal_locally<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<()> {
+        let contributions: Vec<_> = (0..self.num_participants)
+            .map(|_| Contribution::new(rng))
+            .collect();
+        self.commit(contributions.iter().map(|c| c.commitment()).collect())?;
+        self.reveal(contributions.iter().map(|c| c.reveal()).collect())
+    }
+
+    /*
+     * Moves the next roll's commit-reveal forward without waiting and tells whether the dice can
+     * be rolled. Through an exchange this posts a commitment, then a reveal once every
+     * commitment is in, and is ready once every reveal is in.
+     */
+    pub fn exchange<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<bool> {
+        if self.rng.is_some() {
+            return Ok(true);
+        }
+        let Some(exchange) = self.exchange.clone() else {
+            self.commit_and_reveal_locally(rng)?;
+            return Ok(true);
+        };
+
+        let committed = self.transcript.last().is_some_and(|r| r.reveals.is_empty());
+        let round = self.first_round + self.transcript.len() - usize::from(committed);
+        let Some(own) = &self.own else {
+            let own = Contribution::new(rng);
+            exchange.post(round, Post::Commitment, &own.commitment())?;
+            self.own = Some(own);
+            return Ok(false);
+        };
+        if !committed {
+            let Some(commitments) = exchange.collect(round, Post::Commitment)? else {
+                return Ok(false);
+            };
+            ensure!(
+                commitments[exchange.participant] == own.commitment(),
+                FairDiceError::ForeignCommitment(exchange.participant)
+            );
+            exchange.post(round, Post::Reveal, &own.reveal())?;
+            self.commit(commitments)?;
+            return Ok(false);
+        }
+        let Some(reveals) = exchange.collect(round, Post::Reveal)? else {
+            return Ok(false);
+        };
+        self.reveal(reveals)?;
+        self.own = None;
+        Ok(true)
+    }
+
+    pub fn is_revealed(&self) -> bool {
+        self.rng.is_some()
+    }
+
+    /* The next roll needs new contributions */
+    pub fn finish_roll(&mut self) {
+        self.rng = None;
+    }
+
+    pub fn get_transcript(&self) -> &[RollRecord] {
+        &self.transcript
+    }
+}
+
+impl DiceSource for FairDice {
+    fn next_pip(&mut self, spec: &DieSpec) -> Result<Pip> {
+        let rng = self
+            .rng
+            .as_mut()
+            .ok_or_else(|| anyhow!(FairDiceError::NotRevealed))?;
+        let pip = rng.next_pip(spec)?;
+        if let Some(record) = self.transcript.last_mut() {
+            record.pips.push(pip);
+        }
+        Ok(pip)
+    }
+}
+
+/* Replays every roll from its reveals and checks it against the recorded pips */
+pub fn verify(die: &'static DieSpec, transcript: &[RollRecord]) -> Result<()> {
+    for (index, record) in transcript.iter().enumerate() {
+        ensure!(
+            record.reveals.len() == record.commitments.len(),
+            FairDiceError::RollMismatch(index)
+        );
+        for (i, (reveal, commitment)) in record.reveals.iter().zip(&record.commitments).enumerate()
+        {
+            ensure!(
+                sha256(&[reveal]) == *commitment,
+                FairDiceError::RevealMismatch(i)
+            );
+        }
+
+        /* A hand cannot hold more dice than this, so more pips cannot come from a roll */
+        ensure!(
+            record.pips.len() <= Dice::MAX_LEN,
+            FairDiceError::RollMismatch(index)
+        );
+        let mut rng = StdRng::from_seed(record.seed());
+        let mut hand = Hand::with_die_spec(record.pips.len(), die)?;
+        if !record.pips.is_empty() {
+            hand.reroll_dice(&mut rng)?;
+        }
+        ensure!(
+            hand.get_rolled_pips() == record.pips,
+            FairDiceError::RollMismatch(index)
+        );
+    }
+    Ok(())
+}
+
+/* The die on the first line, then one roll per line in the form of "commitments;reveals;pips" */
+pub struct TranscriptFile {
+    path: PathBuf,
+}
+
+impl Default for TranscriptFile {
+    fn default() -> Self {
+        let dir = std::env::var_os("HOME").map_or_else(PathBuf::new, PathBuf::from);
+        Self::new(dir.join(".yahtzee_transcript"))
+    }
+}
+
+impl TranscriptFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn get_path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn load(&self) -> Result<(&'static DieSpec, Vec<RollRecord>)> {
+        let text = fs::read_to_string(&self.path)
+            .with_context(|| format!("could not read {}", self.path.display()))?;
+        let mut lines = text.lines();
+        let die = lines
+            .next()
+            .and_then(|name| DieSpec::ALL.into_iter().find(|d| d.name == name))
+            .ok_or_else(|| anyhow!(FairDiceError::BrokenTranscript))?;
+
+        let hashes = |field: &str| -> Result<Vec<Hash>> {
+            field
+                .split(',')
+                .filter(|h| !h.is_empty())
+                .map(from_hex)
+                .collect()
+        };
+        let mut transcript = vec![];
+        for line in lines {
+            let [commitments, reveals, pips] = line.split(';').collect::<Vec<_>>()[..] else {
+                bail!(FairDiceError::BrokenTranscript);
+            };
+            transcript.push(RollRecord {
+                commitments: hashes(commitments)?,
+                reveals: hashes(reveals)?,
+                pips: pips
+                    .split(' ')
+                    .filter(|p| !p.is_empty())
+                    .map(|p| Pip::new(p.parse()?))
+                    .collect::<Result<_>>()?,
+            });
+        }
+        Ok((die, transcript))
+    }
+
+    pub fn save(&self, die: &DieSpec, transcript: &[RollRecord]) -> Result<()> {
+        let mut text = format!("{}\n", die.name);
+        for record in transcript {
+            let hashes = |hs: &[Hash]| hs.iter().map(to_hex).collect::<Vec<_>>().join(",");
+            let pips: Vec<_> = record.pips.iter().map(|p| p.to_string()).collect();
+            text += &format!(
+                "{};{};{}\n",
+                hashes(&record.commitments),
+                hashes(&record.reveals),
+                pips.join(" ")
+            );
+        }
+        fs::write(&self.path, text)
+            .with_context(|| format!("could not write {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fair::*;
+
+    fn play_rolls(num_rolls: usize) -> FairDice {
+        let mut dice = FairDice::new(2);
+        for _ in 0..num_rolls {
+            dice.commit_and_reveal_locally(&mut rand::thread_rng())
+                .unwrap();
+            Hand::default().reroll_dice(&mut dice).unwrap();
+        }
+        dice
+    }
+
+    #[test]
+    fn commit_reveal_test() {
+        let mut dice = FairDice::new(2);
+        let mut hand = Hand::default();
+        assert!(hand.reroll_dice(&mut dice).is_err());
+
+        let alice = Contribution::new(&mut rand::thread_rng());
+        let bob = Contribution::new(&mut rand::thread_rng());
+        assert!(dice.reveal(vec![alice.reveal(), bob.reveal()]).is_err());
+        assert!(dice.commit(vec![alice.commitment()]).is_err());
+
+        dice.commit(vec![alice.commitment(), bob.commitment()])
+            .unwrap();
+        /* A participant cannot change the contribution after the commitment */
+        let cheat = Contribution::new(&mut rand::thread_rng());
+        assert!(dice.reveal(vec![alice.reveal(), cheat.reveal()]).is_err());
+        dice.reveal(vec![alice.reveal(), bob.reveal()]).unwrap();
+
+        hand.reroll_dice(&mut dice).unwrap();
+        let record = &dice.get_transcript()[0];
+        assert_eq!(record.pips, hand.get_pips());
+        assert!(verify(&DieSpec::D6, dice.get_transcript()).is_ok());
+    }
+
+    #[test]
+    fn verify_test() {
+        let dice = play_rolls(10);
+        let mut transcript = dice.get_transcript().to_vec();
+        assert!(verify(&DieSpec::D6, &transcript).is_ok());
+
+        let pip = transcript[3].pips[0];
+        transcript[3].pips[0] = Pip::new(pip.value() % 6 + 1).unwrap();
+        assert!(verify(&DieSpec::D6, &transcript).is_err());
+
+        transcript[3].pips[0] = pip;
+        transcript[5].reveals[1][0] ^= 1;
+        assert!(verify(&DieSpec::D6, &transcript).is_err());
+
+        transcript[5].reveals[1][0] ^= 1;
+        transcript[7].pips = vec![pip; Dice::MAX_LEN + 1];
+        assert!(verify(&DieSpec::D6, &transcript).is_err());
+    }
+
+    #[test]
+    fn exchange_test() {
+        let dir = std::env::temp_dir().join(format!("yahtzee_exchange_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+
+        /* The other participant runs elsewhere with a random source of its own */
+        let other = Exchange::new(dir.clone(), 1, 2);
+        let contributor = thread::spawn(move || other.contribute(&mut rand::thread_rng()));
+
+        let exchange = Exchange::new(dir.clone(), 0, 2);
+        let mut transcript = vec![];
+        for first_round in [0, 2] {
+            let mut dice = FairDice::with_exchange(exchange.clone(), first_round);
+            for _ in 0..2 {
+                let mut hand = Hand::default();
+                assert!(hand.reroll_dice(&mut dice).is_err());
+                while !dice.exchange(&mut rand::thread_rng()).unwrap() {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                hand.reroll_dice(&mut dice).unwrap();
+                dice.finish_roll();
+            }
+            transcript.extend_from_slice(dice.get_transcript());
+        }
+        exchange
+            .transcript_file()
+            .save(&DieSpec::D6, &transcript)
+            .unwrap();
+        assert_eq!(contributor.join().unwrap().unwrap(), 4);
+
+        /* A host which drops the other participant's reveal is caught */
+        let other = Exchange::new(dir.clone(), 1, 2);
+        transcript[3].reveals[1] = transcript[3].reveals[0];
+        exchange
+            .transcript_file()
+            .save(&DieSpec::D6, &transcript)
+            .unwrap();
+        assert!(other.contribute(&mut rand::thread_rng()).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn transcript_file_test() {
+        let path = std::env::temp_dir().join(format!("yahtzee_fair_{}", std::process::id()));
+        let file = TranscriptFile::new(path.clone());
+        let dice = play_rolls(4);
+        file.save(&DieSpec::POKER, dice.get_transcript()).unwrap();
+
+        let (die, transcript) = file.load().unwrap();
+        assert!(die == &DieSpec::POKER);
+        assert_eq!(transcript, dice.get_transcript());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,26 +1,106 @@
+use crate::bot::Seat;
+use crate::fair::RollRecord;
+use crate::hand::DieSpec;
+use crate::play::{Play, TurnRecord};
+use crate::rule_set::RuleSet;
 use crate::score_table::ScoreTable;
 use anyhow::{bail, Result};
+use rand::{rngs::StdRng, SeedableRng};
+use std::rc::Rc;
 use thiserror::Error;
 
+/* The SplitMix64 finalizer, which spreads nearby values over the whole range */
+pub fn split_mix(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
 #[derive(Debug, Error)]
 pub enum GameDataError {
     #[error("The game is finished")]
     FinishedGame,
 }
 
+#[derive(Clone, Copy)]
+pub struct GameSettings {
+    pub rolls_per_turn: usize,
+    /* Bank the rolls left unused in a turn for later turns even if the rules do not */
+    pub carries_over_rolls: bool,
+    /* Scorekeeper mode: players roll real dice and type in the pips */
+    pub uses_physical_dice: bool,
+    pub die: &'static DieSpec,
+    pub uses_fair_dice: bool,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            rolls_per_turn: Play::DEFAULT_ROLLS_PER_TURN,
+            carries_over_rolls: false,
+            uses_physical_dice: false,
+            die: &DieSpec::D6,
+            uses_fair_dice: false,
+        }
+    }
+}
+
+impl GameSettings {
+    pub const MIN_ROLLS_PER_TURN: usize = 1;
+    pub const MAX_ROLLS_PER_TURN: usize = 5;
+}
+
 pub struct GameData {
     num_players: usize,
+    seats: Vec<Seat>,
+    rule: Rc<dyn RuleSet>,
+    settings: GameSettings,
+    seed: u64,
     scores: Vec<ScoreTable>,
+    saved_rolls: Vec<usize>,
+    transcript: Vec<RollRecord>,
+    turns: Vec<TurnRecord>,
 }
 
 impl GameData {
-    pub fn new(num_players: usize) -> Self {
+    pub fn new(
+        num_players: usize,
+        rule: Rc<dyn RuleSet>,
+        settings: GameSettings,
+        seed: u64,
+    ) -> Self {
+        Self::with_seats(vec![Seat::Human; num_players], rule, settings, seed)
+    }
+
+    pub fn with_seats(
+        seats: Vec<Seat>,
+        rule: Rc<dyn RuleSet>,
+        settings: GameSettings,
+        seed: u64,
+    ) -> Self {
+        let num_players = seats.len();
+        /* Builds the score lookup at the start rather than on the first frame that needs it */
+        rule.lookup();
         Self {
             num_players,
-            scores: (0..num_players).map(|_| ScoreTable::new()).collect(),
+            seats,
+            scores: (0..num_players)
+                .map(|_| ScoreTable::with_rule_set(rule.clone()))
+                .collect(),
+            rule,
+            settings,
+            seed,
+            saved_rolls: vec![0; num_players],
+            transcript: vec![],
+            turns: vec![],
         }
     }
 
+    pub fn get_rule_set(&self) -> &dyn RuleSet {
+        self.rule.as_ref()
+    }
+
     pub fn get_score_table(&self, player_id: usize) -> &ScoreTable {
         if player_id >= self.num_players {
             panic!(
@@ -43,12 +123,70 @@ impl GameData {
         &mut self.scores[player_id]
     }
 
+    /* The number of rolls the player can use in the next turn */
+    pub fn get_roll_budget(&self, player_id: usize) -> usize {
+        self.settings.rolls_per_turn + self.saved_rolls[player_id]
+    }
+
+    pub fn save_rolls(&mut self, player_id: usize, rolls: usize) {
+        if self.rule.saves_unused_rolls() || self.settings.carries_over_rolls {
+            self.saved_rolls[player_id] = rolls;
+        }
+    }
+
+    pub fn get_settings(&self) -> &GameSettings {
+        &self.settings
+    }
+
+    pub fn get_transcript(&self) -> &[RollRecord] {
+        &self.transcript
+    }
+
+    pub fn extend_transcript(&mut self, records: &[RollRecord]) {
+        self.transcript.extend_from_slice(records);
+    }
+
+    /* Every turn of the game in the order they were played */
+    pub fn get_turns(&self) -> &[TurnRecord] {
+        &self.turns
+    }
+
+    pub fn record_turn(&mut self, record: TurnRecord) {
+        self.turns.push(record);
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /* Each turn has its own random source so that the same seed replays the same rolls. */
+    pub fn turn_rng(&self) -> StdRng {
+        let turn: usize = self.scores.iter().map(|s| s.get_num_filled_scores()).sum();
+        StdRng::seed_from_u64(Self::turn_seed(self.seed, turn as u64))
+    }
+
+    /* Mixed so that nearby seeds do not share the rolls of nearby turns */
+    fn turn_seed(seed: u64, turn: u64) -> u64 {
+        split_mix(split_mix(seed) ^ turn)
+    }
+
     pub fn get_num_players(&self) -> usize {
         self.num_players
     }
 
+    pub fn get_seat(&self, player_id: usize) -> Seat {
+        self.seats[player_id]
+    }
+
+    pub fn get_player_name(&self, player_id: usize) -> String {
+        match self.seats[player_id] {
+            Seat::Human => format!("Player{}", player_id),
+            Seat::Bot(..) => format!("Bot{}", player_id),
+        }
+    }
+
     pub fn current_player_id(&self) -> Result<usize> {
-        if self.scores.iter().all(|st| st.has_all_scores()) {
+        if self.rule.is_game_over(&self.scores) {
             bail!(GameDataError::FinishedGame);
         }
 
@@ -62,3 +200,50 @@ impl GameData {
             .unwrap_or(0))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::game_data::*;
+    use crate::rule_set::Variant;
+
+    #[test]
+    fn roll_budget_test() {
+        let settings = GameSettings {
+            rolls_per_turn: 2,
+            carries_over_rolls: false,
+            uses_physical_dice: false,
+            die: &DieSpec::D6,
+            uses_fair_dice: false,
+        };
+        let mut game_data = GameData::new(2, Variant::Yahtzee.rule_set(), settings, 0);
+        game_data.save_rolls(0, 1);
+        assert_eq!(game_data.get_roll_budget(0), 2);
+
+        let settings = GameSettings {
+            carries_over_rolls: true,
+            ..settings
+        };
+        let mut game_data = GameData::new(2, Variant::Yahtzee.rule_set(), settings, 0);
+        game_data.save_rolls(0, 1);
+        assert_eq!(game_data.get_roll_budget(0), 3);
+        assert_eq!(game_data.get_roll_budget(1), 2);
+
+        let mut game_data =
+            GameData::new(1, Variant::MaxiYatzy.rule_set(), GameSettings::default(), 0);
+        game_data.save_rolls(0, 2);
+        assert_eq!(
+            game_data.get_roll_budget(0),
+            Play::DEFAULT_ROLLS_PER_TURN + 2
+        );
+    }
+
+    #[test]
+    fn turn_seed_test() {
+        let turns = |seed| -> Vec<_> { (0..40).map(|t| GameData::turn_seed(seed, t)).collect() };
+        assert_eq!(turns(42), turns(42));
+        for (a, b) in [(41, 42), (42, 43), (0, 1), (u64::MAX, 0)] {
+            let (a, b) = (turns(a), turns(b));
+            assert!(a.iter().all(|s| !b.contains(s)));
+        }
+    }
+}
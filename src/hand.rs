@@ -1,27 +1,198 @@
+use crate::dice::{Dice, Pip};
 use anyhow::{anyhow, bail, ensure, Result};
+use enum_iterator::Sequence;
 use rand::Rng;
+use std::cmp::Reverse;
+use std::fmt;
 use thiserror::Error;
 
+#[derive(PartialEq, Eq)]
+pub struct Face {
+    pub value: u32,
+    pub label: &'static str,
+}
+
+/* What the faces of a die score and how they are shown */
+#[derive(PartialEq, Eq)]
+pub struct DieSpec {
+    pub name: &'static str,
+    pub faces: &'static [Face],
+    /* Faces are drawn as pips rather than as their labels */
+    pub shows_pips: bool,
+}
+
+impl DieSpec {
+    pub const D6: Self = Self {
+        name: "Standard d6",
+        faces: &[
+            Face {
+                value: 1,
+                label: "1",
+            },
+            Face {
+                value: 2,
+                label: "2",
+            },
+            Face {
+                value: 3,
+                label: "3",
+            },
+            Face {
+                value: 4,
+                label: "4",
+            },
+            Face {
+                value: 5,
+                label: "5",
+            },
+            Face {
+                value: 6,
+                label: "6",
+            },
+        ],
+        shows_pips: true,
+    };
+    pub const D8: Self = Self {
+        name: "d8",
+        faces: &[
+            Face {
+                value: 1,
+                label: "1",
+            },
+            Face {
+                value: 2,
+                label: "2",
+            },
+            Face {
+                value: 3,
+                label: "3",
+            },
+            Face {
+                value: 4,
+                label: "4",
+            },
+            Face {
+                value: 5,
+                label: "5",
+            },
+            Face {
+                value: 6,
+                label: "6",
+            },
+            Face {
+                value: 7,
+                label: "7",
+            },
+            Face {
+                value: 8,
+                label: "8",
+            },
+        ],
+        shows_pips: true,
+    };
+    /* Ranked from nine to ace so that any rule set can score them like a d6 */
+    pub const POKER: Self = Self {
+        name: "Poker dice",
+        faces: &[
+            Face {
+                value: 1,
+                label: "9",
+            },
+            Face {
+                value: 2,
+                label: "10",
+            },
+            Face {
+                value: 3,
+                label: "J",
+            },
+            Face {
+                value: 4,
+                label: "Q",
+            },
+            Face {
+                value: 5,
+                label: "K",
+            },
+            Face {
+                value: 6,
+                label: "A",
+            },
+        ],
+        shows_pips: false,
+    };
+    pub const ALL: [&'static Self; 3] = [&Self::D6, &Self::D8, &Self::POKER];
+    /* The highest face among ALL, which bounds the pips Dice can count */
+    pub const MAX_VALUE: u32 = {
+        let mut max = 0;
+        let mut i = 0;
+        while i < Self::ALL.len() {
+            let faces = Self::ALL[i].faces;
+            let mut j = 0;
+            while j < faces.len() {
+                if faces[j].value > max {
+                    max = faces[j].value;
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+        max
+    };
+
+    pub fn next_cycle(&self) -> &'static Self {
+        let pos = Self::ALL.iter().position(|&d| d == self).unwrap_or(0);
+        Self::ALL[(pos + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous_cycle(&self) -> &'static Self {
+        let pos = Self::ALL.iter().position(|&d| d == self).unwrap_or(0);
+        Self::ALL[(pos + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub fn has_value(&self, value: u32) -> bool {
+        self.faces.iter().any(|f| f.value == value)
+    }
+
+    pub fn label_of(&self, pip: Pip) -> Option<&'static str> {
+        self.faces
+            .iter()
+            .find(|f| f.value == pip.value())
+            .map(|f| f.label)
+    }
+}
+
+impl fmt::Display for DieSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name)
+    }
+}
+
 pub struct Die {
-    pip: u32,
+    pip: Pip,
     is_held: bool,
 }
 
 impl Die {
+    /* Pips of the standard six-sided die */
     pub const PIPS: [u32; 6] = [1, 2, 3, 4, 5, 6];
 
-    fn new() -> Self {
-        Self {
-            pip: Self::PIPS[rand::thread_rng().gen_range(0..Self::PIPS.len())],
+    fn new<S: DiceSource + ?Sized>(spec: &DieSpec, source: &mut S) -> Result<Self> {
+        Ok(Self {
+            pip: source.next_pip(spec)?,
             is_held: false,
-        }
+        })
     }
 
-    fn gen_n_dice(num: usize) -> Vec<Die> {
-        (0..num).map(|_| Die::new()).collect()
+    fn gen_n_dice<S: DiceSource + ?Sized>(
+        num: usize,
+        spec: &DieSpec,
+        source: &mut S,
+    ) -> Result<Vec<Die>> {
+        (0..num).map(|_| Die::new(spec, source)).collect()
     }
 
-    fn pip(&self) -> u32 {
+    fn pip(&self) -> Pip {
         self.pip
     }
 
@@ -34,12 +205,48 @@ impl Die {
     }
 }
 
+/* Where the pips of rolled dice come from */
+pub trait DiceSource {
+    fn next_pip(&mut self, spec: &DieSpec) -> Result<Pip>;
+}
+
+impl<R: Rng + ?Sized> DiceSource for R {
+    fn next_pip(&mut self, spec: &DieSpec) -> Result<Pip> {
+        Pip::new(spec.faces[self.gen_range(0..spec.faces.len())].value)
+    }
+}
+
+/* Pips of physical dice entered by hand */
+pub struct ManualDice {
+    pips: std::vec::IntoIter<Pip>,
+}
+
+impl ManualDice {
+    pub fn new(pips: Vec<u32>, spec: &DieSpec) -> Result<Self> {
+        if let Some(&pip) = pips.iter().find(|&&p| !spec.has_value(p)) {
+            bail!(HandOpError::InvalidPip(pip));
+        }
+        let pips = pips.into_iter().map(Pip::new).collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            pips: pips.into_iter(),
+        })
+    }
+}
+
+impl DiceSource for ManualDice {
+    fn next_pip(&mut self, _spec: &DieSpec) -> Result<Pip> {
+        self.pips
+            .next()
+            .ok_or_else(|| anyhow!(HandOpError::NotEnoughPips))
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum HandOpError {
     #[error("There is no die in pos {0}")]
     NoDie(usize),
-    #[error("The number of dice must be {} or less", Hand::DICE_NUM)]
-    OutOfPossibleRange,
+    #[error("The number of dice must be {0} or less")]
+    OutOfPossibleRange(usize),
     #[error("No dice to roll")]
     NoDiceToRoll,
     #[error("No dice to reroll")]
@@ -48,52 +255,94 @@ pub enum HandOpError {
     TooBigHand,
     #[error("Hand is not fully filled")]
     NotFullyFilled,
-    #[error("Return not fully filled hand")]
-    ReturnShortHand(Vec<u32>),
+    #[error("{0} is not a pip of a die")]
+    InvalidPip(u32),
+    #[error("Not enough pips are entered")]
+    NotEnoughPips,
 }
 
-impl HandOpError {
-    pub fn unwrap_pips(pips: Result<Vec<u32>>) -> Vec<u32> {
-        match pips {
-            Ok(p) => p,
-            Err(e) => match e.downcast_ref::<Self>() {
-                Some(Self::ReturnShortHand(p)) => p.to_vec(),
-                _ => panic!("{:?}", e),
-            },
+/* How the dice are lined up after a roll */
+#[derive(PartialEq, Eq, Sequence, Clone, Copy)]
+pub enum Arrangement {
+    Stable,
+    Sorted,
+    Grouped,
+}
+
+impl fmt::Display for Arrangement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Arrangement::Stable => f.pad("Stable"),
+            Arrangement::Sorted => f.pad("Sorted"),
+            Arrangement::Grouped => f.pad("Grouped"),
         }
     }
 }
 
 pub struct Hand {
+    dice_num: usize,
+    die: &'static DieSpec,
     dice: Vec<Die>,
+    /* Pips of the dice rolled last in the order they were rolled */
+    rolled: Vec<Pip>,
 }
 
 impl Default for Hand {
     fn default() -> Self {
-        Self::new()
+        Self::empty(Self::DEFAULT_DICE_NUM, &DieSpec::D6)
     }
 }
 
+const _: () = assert!(Hand::DEFAULT_DICE_NUM <= Dice::MAX_LEN);
+
 impl Hand {
-    pub const DICE_NUM: usize = 5;
+    pub const DEFAULT_DICE_NUM: usize = 5;
 
-    pub fn new() -> Self {
-        Hand { dice: vec![] }
+    /* A hand never holds more dice than Dice can, so that the dice can always be scored */
+    pub fn with_die_spec(dice_num: usize, die: &'static DieSpec) -> Result<Self> {
+        ensure!(
+            dice_num <= Dice::MAX_LEN,
+            HandOpError::OutOfPossibleRange(Dice::MAX_LEN)
+        );
+        Ok(Self::empty(dice_num, die))
     }
 
-    pub fn get_pips(&self) -> Result<Vec<u32>> {
-        ensure!(self.dice.len() <= Self::DICE_NUM, HandOpError::TooBigHand);
-
-        let pips: Vec<_> = self.dice.iter().map(|d| d.pip()).collect();
-        if pips.len() == Self::DICE_NUM {
-            Ok(pips)
-        } else {
-            Err(anyhow!(HandOpError::ReturnShortHand(pips)))
+    fn empty(dice_num: usize, die: &'static DieSpec) -> Self {
+        Hand {
+            dice_num,
+            die,
+            dice: vec![],
+            rolled: vec![],
         }
     }
 
+    pub fn get_dice_num(&self) -> usize {
+        self.dice_num
+    }
+
+    pub fn get_die_spec(&self) -> &'static DieSpec {
+        self.die
+    }
+
+    /* Pips in the order of the slots, fewer than the number of dice before the first roll */
+    pub fn get_pips(&self) -> Vec<Pip> {
+        self.dice.iter().map(|d| d.pip()).collect()
+    }
+
+    pub fn get_dice(&self) -> Result<Dice> {
+        Dice::new(self.get_pips())
+    }
+
+    /* The dice which the next roll keeps */
+    pub fn get_held_dice(&self) -> Result<Dice> {
+        Dice::new(self.dice.iter().filter(|d| d.is_held()).map(|d| d.pip()))
+    }
+
     pub fn is_held(&self, pos: usize) -> Result<bool> {
-        ensure!(pos < Self::DICE_NUM, HandOpError::OutOfPossibleRange);
+        ensure!(
+            pos < self.dice_num,
+            HandOpError::OutOfPossibleRange(self.dice_num)
+        );
 
         if let Some(d) = self.dice.get(pos) {
             Ok(d.is_held())
@@ -103,13 +352,16 @@ impl Hand {
     }
 
     pub fn is_held_all(&self) -> Result<bool> {
-        ensure!(self.dice.len() <= Self::DICE_NUM, HandOpError::TooBigHand);
+        ensure!(self.dice.len() <= self.dice_num, HandOpError::TooBigHand);
 
-        Ok(self.dice.len() == Self::DICE_NUM && self.dice.iter().all(|d| d.is_held))
+        Ok(self.dice.len() == self.dice_num && self.dice.iter().all(|d| d.is_held))
     }
 
     pub fn hold(&mut self, pos: usize, hold: bool) -> Result<()> {
-        ensure!(pos < Self::DICE_NUM, HandOpError::OutOfPossibleRange);
+        ensure!(
+            pos < self.dice_num,
+            HandOpError::OutOfPossibleRange(self.dice_num)
+        );
 
         if let Some(d) = self.dice.get_mut(pos) {
             d.hold(hold);
@@ -120,7 +372,7 @@ impl Hand {
     }
 
     pub fn hold_all(&mut self) -> Result<()> {
-        ensure!(self.dice.len() <= Self::DICE_NUM, HandOpError::TooBigHand);
+        ensure!(self.dice.len() <= self.dice_num, HandOpError::TooBigHand);
 
         self.dice.iter_mut().for_each(|d| d.hold(true));
         if self.is_held_all()? {
@@ -130,61 +382,118 @@ impl Hand {
         }
     }
 
-    fn fill_dice(&mut self) -> Result<()> {
-        ensure!(self.dice.len() < Self::DICE_NUM, HandOpError::NoDiceToRoll);
+    pub fn get_num_dice_to_roll(&self) -> usize {
+        self.dice_num - self.dice.iter().filter(|d| d.is_held()).count()
+    }
+
+    fn fill_dice<S: DiceSource + ?Sized>(&mut self, source: &mut S) -> Result<()> {
+        ensure!(self.dice.len() < self.dice_num, HandOpError::NoDiceToRoll);
 
-        let num = Self::DICE_NUM - self.dice.len();
-        self.dice.extend(Die::gen_n_dice(num));
+        let num = self.dice_num - self.dice.len();
+        let dice = Die::gen_n_dice(num, self.die, source)?;
+        self.rolled.extend(dice.iter().map(|d| d.pip()));
+        self.dice.extend(dice);
         Ok(())
     }
 
-    fn remove_dice(&mut self) {
-        let to_be_removed: Vec<_> = self
-            .dice
-            .iter()
-            .enumerate()
-            .rev()
-            .filter_map(|(i, d)| if !d.is_held() { Some(i) } else { None })
-            .collect();
-        for i in to_be_removed {
-            self.dice.remove(i);
-        }
+    pub fn get_rolled_pips(&self) -> Vec<Pip> {
+        self.rolled.clone()
     }
 
-    pub fn reroll_dice(&mut self) -> Result<()> {
+    pub fn reroll_dice<S: DiceSource + ?Sized>(&mut self, source: &mut S) -> Result<()> {
         ensure!(!self.is_held_all()?, HandOpError::NoDiceToReroll);
 
-        self.remove_dice();
-        self.fill_dice()
+        /* The dice not held are rolled again in their own slots */
+        self.rolled.clear();
+        for d in self.dice.iter_mut().filter(|d| !d.is_held()) {
+            *d = Die::new(self.die, source)?;
+            self.rolled.push(d.pip());
+        }
+        if self.dice.len() < self.dice_num {
+            self.fill_dice(source)?;
+        }
+        Ok(())
+    }
+
+    pub fn arrange(&mut self, arrangement: Arrangement) {
+        match arrangement {
+            Arrangement::Stable => (),
+            Arrangement::Sorted => self.dice.sort_by_key(|d| d.pip()),
+            Arrangement::Grouped => {
+                let pips: Vec<_> = self.dice.iter().map(|d| d.pip()).collect();
+                let count = |pip| pips.iter().filter(|&&p| p == pip).count();
+                self.dice
+                    .sort_by_key(|d| (Reverse(count(d.pip())), d.pip()));
+            }
+        }
+    }
+
+    pub fn swap(&mut self, pos: usize, other: usize) -> Result<()> {
+        ensure!(pos < self.dice.len(), HandOpError::NoDie(pos));
+        ensure!(other < self.dice.len(), HandOpError::NoDie(other));
+        self.dice.swap(pos, other);
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::hand::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn values(pips: Vec<Pip>) -> Vec<u32> {
+        pips.into_iter().map(|p| p.value()).collect()
+    }
 
     fn gen_hand_with_n_dice(num: usize) -> Hand {
-        if num > Hand::DICE_NUM {
+        if num > Hand::DEFAULT_DICE_NUM {
             panic!("num is too big")
         }
         Hand {
-            dice: Die::gen_n_dice(num),
+            dice_num: Hand::DEFAULT_DICE_NUM,
+            die: &DieSpec::D6,
+            dice: Die::gen_n_dice(num, &DieSpec::D6, &mut rand::thread_rng()).unwrap(),
+            rolled: vec![],
         }
     }
 
     #[test]
     fn new_test() {
-        for num in 0..=Hand::DICE_NUM {
+        for num in 0..=Hand::DEFAULT_DICE_NUM {
             let h = gen_hand_with_n_dice(num);
             assert_eq!(h.dice.len(), num);
             for d in h.dice.iter() {
-                assert!(Die::PIPS.contains(&d.pip));
+                assert!(Die::PIPS.contains(&d.pip.value()));
                 assert!(!d.is_held);
                 assert_eq!(d.is_held, d.is_held());
             }
         }
     }
 
+    #[test]
+    fn dice_num_test() {
+        let mut h = Hand::with_die_spec(6, &DieSpec::D6).unwrap();
+        h.reroll_dice(&mut rand::thread_rng()).unwrap();
+        assert_eq!(values(h.get_pips()).len(), 6);
+        h.hold(5, true).unwrap();
+        assert!(h.is_held(5).unwrap());
+        match h.hold(6, true) {
+            Ok(..) => panic!("Must not return Ok"),
+            Err(e) => match e.downcast_ref::<HandOpError>() {
+                Some(HandOpError::OutOfPossibleRange(6)) => (),
+                _ => panic!("Shuld return HandOpError::OutOfPossibleRange"),
+            },
+        }
+
+        match Hand::with_die_spec(Dice::MAX_LEN + 1, &DieSpec::D6) {
+            Ok(..) => panic!("Must not return Ok"),
+            Err(e) => match e.downcast_ref::<HandOpError>() {
+                Some(HandOpError::OutOfPossibleRange(6)) => (),
+                _ => panic!("Shuld return HandOpError::OutOfPossibleRange"),
+            },
+        }
+    }
+
     #[test]
     fn hold_test() {
         let pos = 3;
@@ -198,28 +507,28 @@ mod tests {
     #[test]
     fn fill_dice_test() {
         let mut h0 = gen_hand_with_n_dice(0);
-        h0.fill_dice().unwrap();
-        assert_eq!(h0.dice.len(), Hand::DICE_NUM);
+        h0.fill_dice(&mut rand::thread_rng()).unwrap();
+        assert_eq!(h0.dice.len(), Hand::DEFAULT_DICE_NUM);
 
         const NUM: usize = 3;
         let mut h0 = gen_hand_with_n_dice(NUM);
-        let org = HandOpError::unwrap_pips(h0.get_pips());
+        let org = values(h0.get_pips());
         let holds = [true, false, true];
         holds
             .iter()
             .enumerate()
             .for_each(|(p, &h)| h0.hold(p, h).unwrap());
-        h0.fill_dice().unwrap();
-        assert_eq!(h0.dice.len(), Hand::DICE_NUM);
-        assert_eq!(HandOpError::unwrap_pips(h0.get_pips())[0..NUM], org);
+        h0.fill_dice(&mut rand::thread_rng()).unwrap();
+        assert_eq!(h0.dice.len(), Hand::DEFAULT_DICE_NUM);
+        assert_eq!(values(h0.get_pips())[0..NUM], org);
 
-        let mut h0 = gen_hand_with_n_dice(Hand::DICE_NUM);
-        let holds = [false; Hand::DICE_NUM];
+        let mut h0 = gen_hand_with_n_dice(Hand::DEFAULT_DICE_NUM);
+        let holds = [false; Hand::DEFAULT_DICE_NUM];
         holds
             .iter()
             .enumerate()
             .for_each(|(p, &h)| h0.hold(p, h).unwrap());
-        match h0.fill_dice() {
+        match h0.fill_dice(&mut rand::thread_rng()) {
             Ok(..) => panic!("Must not return Ok"),
             Err(e) => match e.downcast_ref::<HandOpError>() {
                 Some(HandOpError::NoDiceToRoll) => (),
@@ -231,47 +540,45 @@ mod tests {
     #[test]
     fn reroll_dice_test() {
         let mut h0 = gen_hand_with_n_dice(0);
-        h0.reroll_dice().unwrap();
-        assert_eq!(h0.dice.len(), Hand::DICE_NUM);
+        h0.reroll_dice(&mut rand::thread_rng()).unwrap();
+        assert_eq!(h0.dice.len(), Hand::DEFAULT_DICE_NUM);
 
         const NUM: usize = 3;
         let mut h0 = gen_hand_with_n_dice(NUM);
-        let org = HandOpError::unwrap_pips(h0.get_pips());
+        let org = values(h0.get_pips());
         let holds = [true, false, true];
         holds
             .iter()
             .enumerate()
             .for_each(|(p, &h)| h0.hold(p, h).unwrap());
-        h0.reroll_dice().unwrap();
-        assert_eq!(h0.dice.len(), Hand::DICE_NUM);
-        let hold_cnt = holds.iter().filter(|&&h| h).count();
-        assert_eq!(
-            HandOpError::unwrap_pips(h0.get_pips())[0..hold_cnt],
-            [org[0], org[2]]
-        );
-
-        let mut h0 = gen_hand_with_n_dice(Hand::DICE_NUM);
-        let org = HandOpError::unwrap_pips(h0.get_pips());
+        h0.reroll_dice(&mut rand::thread_rng()).unwrap();
+        assert_eq!(h0.dice.len(), Hand::DEFAULT_DICE_NUM);
+        let pips = values(h0.get_pips());
+        assert_eq!([pips[0], pips[2]], [org[0], org[2]]);
+        assert_eq!(values(h0.get_rolled_pips()), [pips[1], pips[3], pips[4]]);
+
+        let mut h0 = gen_hand_with_n_dice(Hand::DEFAULT_DICE_NUM);
+        let org = values(h0.get_pips());
         let holds = [true, false, true, true, false];
         holds
             .iter()
             .enumerate()
             .for_each(|(p, &h)| h0.hold(p, h).unwrap());
-        h0.reroll_dice().unwrap();
-        assert_eq!(h0.dice.len(), Hand::DICE_NUM);
-        let hold_cnt = holds.iter().filter(|&&h| h).count();
-        assert_eq!(
-            HandOpError::unwrap_pips(h0.get_pips())[0..hold_cnt],
-            [org[0], org[2], org[3]]
-        );
-
-        let mut h0 = gen_hand_with_n_dice(Hand::DICE_NUM);
-        let holds = [true; Hand::DICE_NUM];
+        let held = Dice::new([0, 2, 3].map(|p| h0.get_pips()[p])).unwrap();
+        assert_eq!(h0.get_held_dice().unwrap(), held);
+        h0.reroll_dice(&mut rand::thread_rng()).unwrap();
+        assert_eq!(h0.dice.len(), Hand::DEFAULT_DICE_NUM);
+        let pips = values(h0.get_pips());
+        assert_eq!([pips[0], pips[2], pips[3]], [org[0], org[2], org[3]]);
+        assert_eq!(values(h0.get_rolled_pips()), [pips[1], pips[4]]);
+
+        let mut h0 = gen_hand_with_n_dice(Hand::DEFAULT_DICE_NUM);
+        let holds = [true; Hand::DEFAULT_DICE_NUM];
         holds
             .iter()
             .enumerate()
             .for_each(|(p, &h)| h0.hold(p, h).unwrap());
-        match h0.reroll_dice() {
+        match h0.reroll_dice(&mut rand::thread_rng()) {
             Ok(..) => panic!("Must not return Ok"),
             Err(e) => match e.downcast_ref::<HandOpError>() {
                 Some(HandOpError::NoDiceToReroll) => (),
@@ -281,41 +588,89 @@ mod tests {
     }
 
     #[test]
-    fn remove_dice_test() {
-        let mut h0 = gen_hand_with_n_dice(5);
-        let org = HandOpError::unwrap_pips(h0.get_pips());
-        let rms = [true, true, false, false, false];
-        rms.iter()
-            .enumerate()
-            .for_each(|(p, &rm)| h0.hold(p, !rm).unwrap());
-        h0.remove_dice();
-        assert_eq!(
-            HandOpError::unwrap_pips(h0.get_pips()),
-            [org[2], org[3], org[4]]
-        );
+    fn seeded_roll_test() {
+        let roll = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut h = Hand::default();
+            h.reroll_dice(&mut rng).unwrap();
+            h.hold(1, true).unwrap();
+            h.reroll_dice(&mut rng).unwrap();
+            values(h.get_pips())
+        };
+        assert_eq!(roll(42), roll(42));
+        assert!((0..8).any(|seed| roll(seed) != roll(42)));
+    }
 
-        let mut h1 = gen_hand_with_n_dice(5);
-        let org = HandOpError::unwrap_pips(h1.get_pips());
-        let rms = [true, false, true, true, false];
-        rms.iter()
-            .enumerate()
-            .for_each(|(p, &rm)| h1.hold(p, !rm).unwrap());
-        h1.remove_dice();
-        assert_eq!(HandOpError::unwrap_pips(h1.get_pips()), [org[1], org[4]]);
-
-        let mut h2 = gen_hand_with_n_dice(4);
-        let org = HandOpError::unwrap_pips(h2.get_pips());
-        let rms = [true, false, true, false];
-        rms.iter()
-            .enumerate()
-            .for_each(|(p, &rm)| h2.hold(p, !rm).unwrap());
-        h2.remove_dice();
-        assert_eq!(HandOpError::unwrap_pips(h2.get_pips()), [org[1], org[3]]);
-        let rms = [false, true];
-        rms.iter()
-            .enumerate()
-            .for_each(|(p, &rm)| h2.hold(p, !rm).unwrap());
-        h2.remove_dice();
-        assert_eq!(HandOpError::unwrap_pips(h2.get_pips()), [org[1]]);
+    #[test]
+    fn manual_dice_test() {
+        let mut h = Hand::default();
+        h.reroll_dice(&mut ManualDice::new(vec![3, 1, 4, 1, 5], &DieSpec::D6).unwrap())
+            .unwrap();
+        assert_eq!(values(h.get_pips()), vec![3, 1, 4, 1, 5]);
+
+        h.hold(0, true).unwrap();
+        h.hold(2, true).unwrap();
+        assert_eq!(h.get_num_dice_to_roll(), 3);
+        h.reroll_dice(&mut ManualDice::new(vec![6, 6, 2], &DieSpec::D6).unwrap())
+            .unwrap();
+        assert_eq!(values(h.get_pips()), vec![3, 6, 4, 6, 2]);
+
+        assert!(ManualDice::new(vec![1, 7], &DieSpec::D6).is_err());
+        assert!(ManualDice::new(vec![0], &DieSpec::D6).is_err());
+        h.hold(2, true).unwrap();
+        assert!(h
+            .reroll_dice(&mut ManualDice::new(vec![1], &DieSpec::D6).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn die_spec_test() {
+        for spec in DieSpec::ALL {
+            let mut h = Hand::with_die_spec(Hand::DEFAULT_DICE_NUM, spec).unwrap();
+            for _ in 0..20 {
+                h.reroll_dice(&mut rand::thread_rng()).unwrap();
+                let pips = h.get_pips();
+                assert!(pips.iter().all(|&p| spec.has_value(p.value())));
+                assert!(pips.iter().all(|&p| spec.label_of(p).is_some()));
+            }
+        }
+
+        let highest = DieSpec::ALL
+            .iter()
+            .flat_map(|d| d.faces)
+            .map(|f| f.value)
+            .max();
+        assert_eq!(highest, Some(Pip::MAX));
+        assert!(ManualDice::new(vec![7, 8], &DieSpec::D8).is_ok());
+        assert!(ManualDice::new(vec![7], &DieSpec::POKER).is_err());
+        assert_eq!(DieSpec::POKER.label_of(Pip::new(3).unwrap()), Some("J"));
+        assert_eq!(DieSpec::D6.label_of(Pip::new(7).unwrap()), None);
+    }
+
+    #[test]
+    fn arrange_test() {
+        let mut h = Hand::default();
+        h.reroll_dice(&mut ManualDice::new(vec![5, 2, 6, 2, 5], &DieSpec::D6).unwrap())
+            .unwrap();
+        h.hold(2, true).unwrap();
+
+        h.arrange(Arrangement::Stable);
+        assert_eq!(values(h.get_pips()), vec![5, 2, 6, 2, 5]);
+        h.arrange(Arrangement::Grouped);
+        assert_eq!(values(h.get_pips()), vec![2, 2, 5, 5, 6]);
+        h.arrange(Arrangement::Sorted);
+        assert_eq!(values(h.get_pips()), vec![2, 2, 5, 5, 6]);
+        /* The hold moves together with the die */
+        assert!(h.is_held(4).unwrap());
+
+        h.reroll_dice(&mut ManualDice::new(vec![3, 3, 1, 3], &DieSpec::D6).unwrap())
+            .unwrap();
+        h.arrange(Arrangement::Grouped);
+        assert_eq!(values(h.get_pips()), vec![3, 3, 3, 1, 6]);
+
+        h.swap(0, 4).unwrap();
+        assert_eq!(values(h.get_pips()), vec![6, 3, 3, 1, 3]);
+        assert!(h.is_held(0).unwrap());
+        assert!(h.swap(0, 5).is_err());
     }
 }
@@ -1,26 +1,41 @@
 mod app;
 mod assets;
+mod bot;
+mod daily;
+mod dice;
 mod events;
+mod fair;
 mod game_data;
 mod hand;
+mod odds;
 mod play;
+mod review;
+mod rule_set;
+mod score_lookup;
 mod score_table;
 mod scoring;
+mod solver;
+mod stats;
 mod ui;
 
 use crate::app::{App, AppReturn};
-use crate::events::Events;
+use crate::events::{Events, TICK_RATE};
+use crate::fair::{Exchange, TranscriptFile};
+use crate::score_table::ScoreColumn;
+use crate::solver::Solver;
+use crate::stats::StatsFile;
 use crate::ui::draw_ui;
-use anyhow::Result;
+use anyhow::{bail, ensure, Context, Result};
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use rand::{rngs::StdRng, SeedableRng};
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     Terminal,
 };
-use std::{cell::RefCell, io, panic, rc::Rc, time::Duration};
+use std::{cell::RefCell, fs, io, panic, path::PathBuf, rc::Rc};
 
 struct TuiYatzee<B>
 where
@@ -38,7 +53,7 @@ where
     fn new(app: Rc<RefCell<App>>, terminal: Terminal<B>) -> Self {
         Self {
             app,
-            events: Events::new(Duration::from_millis(30)),
+            events: Events::new(TICK_RATE),
             terminal,
         }
     }
@@ -58,7 +73,134 @@ where
     }
 }
 
+struct Args {
+    seed: Option<u64>,
+    prints_stats: bool,
+    transcript: Option<PathBuf>,
+    solves: bool,
+    /* Where the participants of fair dice post their contributions */
+    exchange: Option<PathBuf>,
+    /* Whether this process only contributes to the fair dice of a game played elsewhere */
+    contributes: bool,
+    participant: usize,
+    num_participants: usize,
+}
+
+/* The number of games which `--solve` plays by the optimal strategy */
+const SOLVER_GAMES: u32 = 1000;
+
+/*
+ * `--seed <u64>` replays a whole session with the same rolls, `--stats` prints the dice audit,
+ * `--verify <path>` checks that the rolls of a fair dice transcript follow from its reveals and
+ * `--solve` reports the expected score of the optimal strategy for solitaire Yahtzee.
+ * `--exchange <dir>` rolls fair dice with contributions posted to the directory, where each other
+ * participant runs `--contribute <dir> --participant <n>`. `--participants <n>` counts everyone.
+ */
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args> {
+    let mut parsed = Args {
+        seed: None,
+        prints_stats: false,
+        transcript: None,
+        solves: false,
+        exchange: None,
+        contributes: false,
+        participant: 1,
+        num_participants: 2,
+    };
+    while let Some(arg) = args.next() {
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .with_context(|| format!("{} requires a value", name))
+        };
+        match name {
+            "--stats" => parsed.prints_stats = true,
+            "--solve" => parsed.solves = true,
+            "--verify" => parsed.transcript = Some(PathBuf::from(value()?)),
+            "--seed" => {
+                parsed.seed = Some(
+                    value()?
+                        .parse()
+                        .context("--seed must be an unsigned integer")?,
+                )
+            }
+            "--exchange" => parsed.exchange = Some(PathBuf::from(value()?)),
+            "--contribute" => {
+                parsed.exchange = Some(PathBuf::from(value()?));
+                parsed.contributes = true;
+            }
+            "--participant" => {
+                parsed.participant = value()?
+                    .parse()
+                    .context("--participant must be an unsigned integer")?
+            }
+            "--participants" => {
+                parsed.num_participants = value()?
+                    .parse()
+                    .context("--participants must be an unsigned integer")?
+            }
+            _ => bail!("unknown argument: {}", arg),
+        }
+    }
+    /* The player's own machine is participant 0 */
+    ensure!(
+        !parsed.contributes || (1..parsed.num_participants).contains(&parsed.participant),
+        "--participant must be from 1 to one less than --participants"
+    );
+    Ok(parsed)
+}
+
 fn main() -> Result<()> {
+    let args = parse_args(std::env::args().skip(1))?;
+    if args.prints_stats {
+        println!("{}", StatsFile::default().load()?.report());
+        return Ok(());
+    }
+    if let Some(path) = args.transcript {
+        let (die, transcript) = TranscriptFile::new(path).load()?;
+        fair::verify(die, &transcript)?;
+        println!("All {} rolls are verified.", transcript.len());
+        return Ok(());
+    }
+    if let (true, Some(dir)) = (args.contributes, &args.exchange) {
+        fs::create_dir_all(dir).with_context(|| format!("could not create {}", dir.display()))?;
+        let exchange = Exchange::new(dir.clone(), args.participant, args.num_participants);
+        println!(
+            "Contributing to the rolls in {} until the game is over.",
+            dir.display()
+        );
+        let rolls = exchange.contribute(&mut rand::thread_rng())?;
+        println!(
+            "All {} rolls are verified with this participant's reveals.",
+            rolls
+        );
+        return Ok(());
+    }
+    if args.solves {
+        let mut solver = Solver::new();
+        let expected = solver.expected_final_score(&ScoreColumn::new());
+        println!("The optimal strategy scores {:.2} on average.", expected);
+        let mut rng = match args.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let mut total = 0;
+        for _ in 0..SOLVER_GAMES {
+            total += solver.play_out(&mut rng)?;
+        }
+        println!(
+            "It scored {:.2} on average in {} games.",
+            f64::from(total) / f64::from(SOLVER_GAMES),
+            SOLVER_GAMES
+        );
+        return Ok(());
+    }
+
     let panic_hook = panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         if let Err(e) = execute!(io::stdout(), LeaveAlternateScreen) {
@@ -74,13 +216,28 @@ fn main() -> Result<()> {
     execute!(io::stdout(), EnterAlternateScreen)?;
 
     let terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
-    let app = Rc::new(RefCell::new(App::new()));
-    let mut tui_yahtzee = TuiYatzee::new(app, terminal);
+    let exchange = match args.exchange {
+        Some(dir) => {
+            fs::create_dir_all(&dir)
+                .with_context(|| format!("could not create {}", dir.display()))?;
+            let exchange = Exchange::new(dir, 0, args.num_participants);
+            ensure!(
+                !exchange.transcript_file().get_path().exists(),
+                "{} holds a finished game, use a new directory",
+                exchange.get_dir().display()
+            );
+            Some(exchange)
+        }
+        None => None,
+    };
+    let app = Rc::new(RefCell::new(App::with_options(args.seed, exchange)));
+    let mut tui_yahtzee = TuiYatzee::new(app.clone(), terminal);
 
     let ret = tui_yahtzee.start();
 
     execute!(io::stdout(), LeaveAlternateScreen)?;
     disable_raw_mode()?;
 
-    ret
+    let saved = app.borrow().save_statistics();
+    ret.and(saved)
 }
@@ -0,0 +1,204 @@
+use crate::dice::{Dice, Pip};
+use crate::hand::DieSpec;
+use crate::rule_set::RuleSet;
+use crate::scoring::Boxes;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/* How a box may turn out by the end of the turn if the player chases it */
+#[derive(Clone, Copy)]
+pub struct BoxOdds {
+    pub b: Boxes,
+    /* The chance that the box scores anything */
+    pub chance: f64,
+    pub expected_score: f64,
+}
+
+/* Chances of each box for every hold of a rule set's dice, worked out for more rolls as needed */
+pub struct Odds {
+    boxes: Vec<Boxes>,
+    /* The faces of the die, which are equally likely */
+    pips: Vec<Pip>,
+    /* Every hold of zero to all the dice, full hands first */
+    keeps: Vec<Dice>,
+    keep_index: HashMap<Dice, usize>,
+    num_hands: usize,
+    /* The holds with one more die for each face */
+    keep_children: Vec<Vec<usize>>,
+    /* The holds with one less die for each face, or the hold itself without such a die */
+    keep_parents: Vec<Vec<usize>>,
+    /* What each full hand is worth to each target, which is a box's chance or score */
+    targets: Vec<Vec<f64>>,
+    /* Index n has the value of each hold to each target with n rolls left */
+    stages: Vec<Vec<Vec<f64>>>,
+}
+
+impl Odds {
+    pub fn new(rule: &dyn RuleSet, die: &DieSpec) -> Result<Self> {
+        let dice_num = rule.dice_num();
+        let pips = die
+            .faces
+            .iter()
+            .map(|f| Pip::new(f.value))
+            .collect::<Result<Vec<_>>>()?;
+        let keeps: Vec<_> = (0..=dice_num)
+            .rev()
+            .flat_map(|n| Dice::multisets(n, &pips))
+            .collect();
+        let keep_index: HashMap<_, _> = keeps.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+        let num_hands = keeps.iter().take_while(|k| k.len() == dice_num).count();
+        let keep_children: Vec<Vec<usize>> = keeps
+            .iter()
+            .map(|keep| {
+                if keep.len() == dice_num {
+                    return vec![];
+                }
+                pips.iter()
+                    .map(|&p| {
+                        let mut dice = *keep;
+                        dice.push(p).expect("a hold has room for another die");
+                        keep_index[&dice]
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut keep_parents: Vec<Vec<usize>> =
+            (0..keeps.len()).map(|k| vec![k; pips.len()]).collect();
+        for (k, children) in keep_children.iter().enumerate() {
+            for (face, &child) in children.iter().enumerate() {
+                keep_parents[child][face] = k;
+            }
+        }
+
+        let boxes = rule.boxes().to_vec();
+        let targets = boxes
+            .iter()
+            .flat_map(|&b| {
+                let scores: Vec<_> = keeps[..num_hands]
+                    .iter()
+                    .map(|hand| rule.look_up_score(b, hand))
+                    .collect();
+                [
+                    scores.iter().map(|&s| f64::from(u8::from(s > 0))).collect(),
+                    scores.iter().map(|&s| f64::from(s)).collect(),
+                ]
+            })
+            .collect();
+
+        Ok(Self {
+            boxes,
+            pips,
+            keeps,
+            keep_index,
+            num_hands,
+            keep_children,
+            keep_parents,
+            targets,
+            stages: vec![],
+        })
+    }
+
+    /* The odds of each box for the dice held with the rolls left, None if they cannot be held */
+    pub fn get(&mut self, held: &Dice, remaining_rolls: usize) -> Option<Vec<BoxOdds>> {
+        let &k = self.keep_index.get(held)?;
+        /* Holding every die scores them without rolling again */
+        let values: Vec<f64> = if k < self.num_hands {
+            self.targets.iter().map(|t| t[k]).collect()
+        } else if remaining_rolls == 0 {
+            return None;
+        } else {
+            self.solve(remaining_rolls);
+            self.stages[remaining_rolls].iter().map(|s| s[k]).collect()
+        };
+        Some(
+            self.boxes
+                .iter()
+                .zip(values.chunks(2))
+                .map(|(&b, v)| BoxOdds {
+                    b,
+                    chance: v[0],
+                    expected_score: v[1],
+                })
+                .collect(),
+        )
+    }
+
+    /* Works out the stages up to the rolls left, each from the one before */
+    fn solve(&mut self, remaining_rolls: usize) {
+        if self.stages.is_empty() {
+            /* With no rolls left only the full hands count */
+            self.stages.push(self.targets.clone());
+        }
+        let probability = 1.0 / self.pips.len() as f64;
+        while self.stages.len() <= remaining_rolls {
+            let stage = self.stages[self.stages.len() - 1]
+                .iter()
+                .map(|last| {
+                    /* The best of each full hand and the holds within it after the last roll */
+                    let mut best = last.clone();
+                    if self.stages.len() > 1 {
+                        for k in (0..self.keeps.len()).rev() {
+                            for &parent in &self.keep_parents[k] {
+                                if best[parent] > best[k] {
+                                    best[k] = best[parent];
+                                }
+                            }
+                        }
+                    }
+                    let mut values = vec![0.0; self.keeps.len()];
+                    values[..self.num_hands].copy_from_slice(&best[..self.num_hands]);
+                    for k in self.num_hands..self.keeps.len() {
+                        values[k] = self.keep_children[k]
+                            .iter()
+                            .map(|&c| values[c])
+                            .sum::<f64>()
+                            * probability;
+                    }
+                    values
+                })
+                .collect();
+            self.stages.push(stage);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::odds::*;
+    use crate::rule_set::Variant;
+
+    fn odds_of(odds: &[BoxOdds], b: Boxes) -> BoxOdds {
+        *odds.iter().find(|o| o.b == b).unwrap()
+    }
+
+    #[test]
+    fn odds_test() {
+        let rule = Variant::Yahtzee.rule_set();
+        let mut odds = Odds::new(rule.as_ref(), &DieSpec::D6).unwrap();
+
+        /* Chasing a Yahtzee from scratch over a whole turn */
+        let turn = odds.get(&Dice::default(), 3).unwrap();
+        let yahtzee = odds_of(&turn, Boxes::Yahtzee);
+        assert!(
+            (yahtzee.chance - 0.046_029_6).abs() < 1e-6,
+            "{}",
+            yahtzee.chance
+        );
+        assert!((yahtzee.expected_score - 50.0 * yahtzee.chance).abs() < 1e-9);
+        assert_eq!(odds_of(&turn, Boxes::Chance).chance, 1.0);
+
+        /* Four of a kind held with one roll left */
+        let held = Dice::try_from([3, 3, 3, 3]).unwrap();
+        let last = odds.get(&held, 1).unwrap();
+        assert!((odds_of(&last, Boxes::Yahtzee).chance - 1.0 / 6.0).abs() < 1e-9);
+        assert_eq!(odds_of(&last, Boxes::FourOfaAKind).chance, 1.0);
+        assert!((odds_of(&last, Boxes::Chance).expected_score - 15.5).abs() < 1e-9);
+
+        /* Holding every die keeps the hand */
+        let hand = Dice::try_from([2, 3, 4, 5, 6]).unwrap();
+        let now = odds.get(&hand, 2).unwrap();
+        assert_eq!(odds_of(&now, Boxes::LargeStraight).chance, 1.0);
+        assert_eq!(odds_of(&now, Boxes::Yahtzee).chance, 0.0);
+        assert!(odds.get(&held, 0).is_none());
+    }
+}
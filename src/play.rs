@@ -1,5 +1,9 @@
-use crate::hand::Hand;
-use anyhow::{bail, Result};
+use crate::dice::Dice;
+use crate::fair::{FairDice, RollRecord};
+use crate::hand::{Arrangement, DieSpec, Hand, ManualDice};
+use crate::scoring::Boxes;
+use anyhow::{bail, ensure, Result};
+use rand::rngs::StdRng;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -8,56 +12,142 @@ pub enum PlayPhaseError {
     UnexpectedRollCount,
     #[error("Current Play has already rinished")]
     FinishedPlay,
+    #[error("Dice are not entered by hand")]
+    NotManualEntry,
+    #[error("Pips of all rolled dice must be entered")]
+    IncompleteEntry,
 }
 
 pub enum PlayPhase {
     Init,
     Roll(usize),
+    Announce,
     SelectOrReroll(usize),
     Select,
 }
 
 impl PlayPhase {
     pub const INIT_ROLL_COUNT: usize = 1;
-    pub const MAX_ROLL_COUNT: usize = 3;
+}
+
+/* What the scoring rules may know about the current turn besides the dice */
+#[derive(Clone, Copy)]
+pub struct TurnContext {
+    pub roll_count: usize,
+}
+
+impl TurnContext {
+    pub fn new(roll_count: usize) -> Self {
+        Self { roll_count }
+    }
+
+    pub fn is_served(&self) -> bool {
+        self.roll_count == PlayPhase::INIT_ROLL_COUNT
+    }
+}
+
+/* A choice of the dice to keep for another roll */
+#[derive(Clone, Copy)]
+pub struct HoldRecord {
+    pub dice: Dice,
+    pub held: Dice,
+    pub remaining_rolls: usize,
+}
+
+/* Everything a player chose in a turn, kept for the review after the game */
+#[derive(Clone)]
+pub struct TurnRecord {
+    pub player_id: usize,
+    pub holds: Vec<HoldRecord>,
+    /* The dice scored and the rolls left when they were */
+    pub dice: Dice,
+    pub remaining_rolls: usize,
+    pub turn: TurnContext,
+    pub filled: Boxes,
+}
+
+/* Where the settled dice of a roll come from */
+pub enum Roller {
+    Random(Box<StdRng>),
+    /* Physical dice whose pips are typed in by the player */
+    Manual(Vec<u32>),
+    /* Commit-reveal dice which every player contributes to */
+    Fair(Box<FairDice>),
 }
 
 pub struct Play {
     player_id: usize,
+    max_roll_count: usize,
+    announces: bool,
+    roller: Roller,
+    arrangement: Arrangement,
+    rejected_pip: Option<u32>,
     hand: Hand,
     phase: PlayPhase,
+    holds: Vec<HoldRecord>,
 }
 
 impl Play {
-    pub const MAX_ROLL_COUNT: usize = 3;
+    pub const DEFAULT_ROLLS_PER_TURN: usize = 3;
 
-    pub fn new(player_id: usize) -> Self {
-        Self {
+    pub fn new(
+        player_id: usize,
+        dice_num: usize,
+        die: &'static DieSpec,
+        roll_budget: usize,
+        announces: bool,
+        roller: Roller,
+        arrangement: Arrangement,
+    ) -> Result<Self> {
+        Ok(Self {
             player_id,
-            hand: Hand::new(),
+            max_roll_count: roll_budget,
+            announces,
+            roller,
+            arrangement,
+            rejected_pip: None,
+            hand: Hand::with_die_spec(dice_num, die)?,
             phase: PlayPhase::Init,
-        }
+            holds: vec![],
+        })
     }
 
     pub fn progress(&mut self) -> Result<()> {
         self.phase = match self.phase {
             PlayPhase::Init => {
-                self.hand.reroll_dice()?;
+                self.shake_dice()?;
                 PlayPhase::Roll(PlayPhase::INIT_ROLL_COUNT)
             }
             PlayPhase::Roll(count) => {
+                self.settle_dice()?;
+                self.hand.arrange(self.arrangement);
                 self.hand.hold_all()?;
-                if (PlayPhase::INIT_ROLL_COUNT..PlayPhase::MAX_ROLL_COUNT).contains(&count) {
+                if count == PlayPhase::INIT_ROLL_COUNT && self.announces {
+                    PlayPhase::Announce
+                } else if (PlayPhase::INIT_ROLL_COUNT..self.max_roll_count).contains(&count) {
                     PlayPhase::SelectOrReroll(count)
-                } else if count == Self::MAX_ROLL_COUNT {
+                } else if count == self.max_roll_count {
                     PlayPhase::Select
                 } else {
                     bail!(PlayPhaseError::UnexpectedRollCount)
                 }
             }
+            PlayPhase::Announce => {
+                if PlayPhase::INIT_ROLL_COUNT < self.max_roll_count {
+                    PlayPhase::SelectOrReroll(PlayPhase::INIT_ROLL_COUNT)
+                } else {
+                    PlayPhase::Select
+                }
+            }
             PlayPhase::SelectOrReroll(count) => {
-                if (PlayPhase::INIT_ROLL_COUNT..PlayPhase::MAX_ROLL_COUNT).contains(&count) {
-                    self.hand.reroll_dice()?;
+                if (PlayPhase::INIT_ROLL_COUNT..self.max_roll_count).contains(&count) {
+                    let hold = HoldRecord {
+                        dice: self.hand.get_dice()?,
+                        held: self.hand.get_held_dice()?,
+                        remaining_rolls: self.get_remaining_rolls(),
+                    };
+                    self.shake_dice()?;
+                    self.holds.push(hold);
                     PlayPhase::Roll(count + 1)
                 } else {
                     bail!(PlayPhaseError::UnexpectedRollCount)
@@ -68,7 +158,94 @@ impl Play {
         Ok(())
     }
 
+    /* Rerolls the dice in the Roll phase just for the look of rolling dice */
+    pub fn shake_dice(&mut self) -> Result<()> {
+        match self.roller {
+            Roller::Random(..) | Roller::Fair(..) => self.hand.reroll_dice(&mut rand::thread_rng()),
+            Roller::Manual(..) => Ok(()),
+        }
+    }
+
+    /* Only the settled dice come from the game's random source or the entered pips */
+    fn settle_dice(&mut self) -> Result<()> {
+        ensure!(self.is_entry_complete(), PlayPhaseError::IncompleteEntry);
+        match &mut self.roller {
+            Roller::Random(rng) => self.hand.reroll_dice(rng),
+            Roller::Manual(pips) => {
+                let mut dice = ManualDice::new(std::mem::take(pips), self.hand.get_die_spec())?;
+                self.hand.reroll_dice(&mut dice)
+            }
+            Roller::Fair(dice) => {
+                self.hand.reroll_dice(dice.as_mut())?;
+                dice.finish_roll();
+                Ok(())
+            }
+        }
+    }
+
+    /* Fair dice can be settled once every participant's contribution is revealed */
+    pub fn exchange_contributions(&mut self) -> Result<()> {
+        if let Roller::Fair(dice) = &mut self.roller {
+            dice.exchange(&mut rand::thread_rng())?;
+        }
+        Ok(())
+    }
+
+    pub fn enter_pip(&mut self, pip: u32) -> Result<()> {
+        let Roller::Manual(pips) = &mut self.roller else {
+            bail!(PlayPhaseError::NotManualEntry)
+        };
+        if !self.hand.get_die_spec().has_value(pip) {
+            self.rejected_pip = Some(pip);
+            return Ok(());
+        }
+        self.rejected_pip = None;
+        if pips.len() < self.hand.get_num_dice_to_roll() {
+            pips.push(pip);
+        }
+        Ok(())
+    }
+
+    pub fn erase_pip(&mut self) -> Result<()> {
+        let Roller::Manual(pips) = &mut self.roller else {
+            bail!(PlayPhaseError::NotManualEntry)
+        };
+        self.rejected_pip = None;
+        pips.pop();
+        Ok(())
+    }
+
     /* helper functions */
+    pub fn is_manual(&self) -> bool {
+        matches!(self.roller, Roller::Manual(..))
+    }
+
+    pub fn get_entered_pips(&self) -> Option<&[u32]> {
+        match &self.roller {
+            Roller::Manual(pips) => Some(pips),
+            _ => None,
+        }
+    }
+
+    pub fn get_fair_transcript(&self) -> Option<&[RollRecord]> {
+        match &self.roller {
+            Roller::Fair(dice) => Some(dice.get_transcript()),
+            _ => None,
+        }
+    }
+
+    pub fn get_rejected_pip(&self) -> Option<u32> {
+        self.rejected_pip
+    }
+
+    pub fn is_entry_complete(&self) -> bool {
+        match &self.roller {
+            Roller::Manual(pips) => pips.len() == self.hand.get_num_dice_to_roll(),
+            Roller::Fair(dice) => dice.is_revealed(),
+            Roller::Random(..) => true,
+        }
+    }
+
     pub fn get_player_id(&self) -> usize {
         self.player_id
     }
@@ -84,4 +261,58 @@ impl Play {
     pub fn get_phase(&self) -> &PlayPhase {
         &self.phase
     }
+
+    pub fn get_remaining_rolls(&self) -> usize {
+        match self.phase {
+            PlayPhase::Init => self.max_roll_count,
+            PlayPhase::Roll(count) | PlayPhase::SelectOrReroll(count) => {
+                self.max_roll_count - count
+            }
+            PlayPhase::Announce => self.max_roll_count - PlayPhase::INIT_ROLL_COUNT,
+            PlayPhase::Select => 0,
+        }
+    }
+
+    pub fn get_turn_context(&self) -> TurnContext {
+        TurnContext::new(self.max_roll_count - self.get_remaining_rolls())
+    }
+
+    /* The record of the turn if the dice are scored in the box */
+    pub fn record_turn(&self, b: Boxes) -> Result<TurnRecord> {
+        Ok(TurnRecord {
+            player_id: self.player_id,
+            holds: self.holds.clone(),
+            dice: self.hand.get_dice()?,
+            remaining_rolls: self.get_remaining_rolls(),
+            turn: self.get_turn_context(),
+            filled: b,
+        })
+    }
+
+    /* A solitaire play of d6 dice entered by hand, rerolling the first die to use up rolls */
+    #[cfg(test)]
+    pub fn rolled(dice: &[u32], remaining_rolls: usize) -> Self {
+        let mut play = Play::new(
+            0,
+            dice.len(),
+            &DieSpec::D6,
+            Play::DEFAULT_ROLLS_PER_TURN,
+            false,
+            Roller::Manual(vec![]),
+            Arrangement::Stable,
+        )
+        .unwrap();
+        play.progress().unwrap();
+        for &pip in dice {
+            play.enter_pip(pip).unwrap();
+        }
+        play.progress().unwrap();
+        for _ in remaining_rolls..Play::DEFAULT_ROLLS_PER_TURN - 1 {
+            play.get_mut_hand().hold(0, false).unwrap();
+            play.progress().unwrap();
+            play.enter_pip(dice[0]).unwrap();
+            play.progress().unwrap();
+        }
+        play
+    }
 }
@@ -0,0 +1,238 @@
+use crate::dice::Dice;
+use crate::game_data::GameData;
+use crate::play::TurnRecord;
+use crate::score_table::ScoreColumn;
+use crate::scoring::Boxes;
+use crate::solver::{Solver, TurnState};
+use anyhow::{Context, Result};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ReviewError {
+    #[error("The solver does not know the choice in turn {0} of Player{1}")]
+    UnknownChoice(usize, usize),
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Choice {
+    /* Holding all the dice means scoring them without rolling again */
+    Hold(Dice),
+    Fill(Boxes),
+}
+
+/* A choice of a player next to the solver's choice in the same spot */
+pub struct ReviewEntry {
+    /* Counted from 1 for each player */
+    pub round: usize,
+    pub player_id: usize,
+    pub dice: Dice,
+    pub remaining_rolls: usize,
+    pub chosen: Choice,
+    pub best: Choice,
+    /* Expected points given up by the choice */
+    pub loss: f64,
+}
+
+impl ReviewEntry {
+    /* Rounding errors of the solver are not mistakes */
+    const TOLERANCE: f64 = 1e-6;
+
+    pub fn is_best(&self) -> bool {
+        self.loss < Self::TOLERANCE
+    }
+}
+
+/* Every hold and box choice of a finished solitaire-rules game weighed by the solver */
+pub struct Review {
+    num_players: usize,
+    entries: Vec<ReviewEntry>,
+}
+
+impl Review {
+    pub fn new(solver: &mut Solver, game_data: &GameData) -> Result<Self> {
+        let columns = (0..game_data.get_num_players())
+            .map(|_| ScoreColumn::new())
+            .collect();
+        Self::replay(solver, columns, game_data.get_turns())
+    }
+
+    /* Replays the turns from the columns the players started with */
+    fn replay(
+        solver: &mut Solver,
+        mut columns: Vec<ScoreColumn>,
+        turns: &[TurnRecord],
+    ) -> Result<Self> {
+        let num_players = columns.len();
+        let mut entries = vec![];
+        for record in turns {
+            let pid = record.player_id;
+            let column = &mut columns[pid];
+            let round = column.get_num_filled_scores() + 1;
+            let state = TurnState::from_column(column);
+            let unknown = || ReviewError::UnknownChoice(round, pid);
+
+            /* Scoring with rolls left is a choice to hold every die */
+            let stop =
+                (record.remaining_rolls > 0).then_some((record.dice, record.remaining_rolls));
+            let holds = record
+                .holds
+                .iter()
+                .map(|h| (h.dice, h.held, h.remaining_rolls))
+                .chain(stop.map(|(dice, rolls)| (dice, dice, rolls)));
+            for (dice, held, remaining_rolls) in holds {
+                let (best, best_value) = solver.best_hold(state, &dice, remaining_rolls);
+                let value = solver
+                    .hold_value(state, &held, remaining_rolls)
+                    .with_context(unknown)?;
+                entries.push(ReviewEntry {
+                    round,
+                    player_id: pid,
+                    dice,
+                    remaining_rolls,
+                    chosen: Choice::Hold(held),
+                    best: Choice::Hold(best),
+                    loss: (best_value - value).max(0.0),
+                });
+            }
+
+            let values = solver.box_values(state, &record.dice);
+            let &(.., value) = values
+                .iter()
+                .find(|&&(b, ..)| b == record.filled)
+                .with_context(unknown)?;
+            let (best, best_value) = solver.best_box(state, &record.dice).with_context(unknown)?;
+            entries.push(ReviewEntry {
+                round,
+                player_id: pid,
+                dice: record.dice,
+                remaining_rolls: record.remaining_rolls,
+                chosen: Choice::Fill(record.filled),
+                best: Choice::Fill(best),
+                loss: (best_value - value).max(0.0),
+            });
+            column.confirm_dice(record.filled, &record.dice, &record.turn)?;
+        }
+        Ok(Self {
+            num_players,
+            entries,
+        })
+    }
+
+    pub fn get_entries(&self) -> &[ReviewEntry] {
+        &self.entries
+    }
+
+    pub fn get_num_players(&self) -> usize {
+        self.num_players
+    }
+
+    fn entries_of(&self, player_id: usize) -> impl Iterator<Item = &ReviewEntry> {
+        self.entries
+            .iter()
+            .filter(move |e| e.player_id == player_id)
+    }
+
+    /* The share of the player's choices which were as good as the solver's */
+    pub fn get_accuracy(&self, player_id: usize) -> f64 {
+        let (best, total) = self.entries_of(player_id).fold((0, 0), |(best, total), e| {
+            (best + usize::from(e.is_best()), total + 1)
+        });
+        if total == 0 {
+            return 1.0;
+        }
+        best as f64 / total as f64
+    }
+
+    pub fn get_points_lost(&self, player_id: usize) -> f64 {
+        self.entries_of(player_id).map(|e| e.loss).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::play::Play;
+    use crate::review::*;
+    use crate::score_table::ScoreTable;
+
+    fn entry(player_id: usize, loss: f64) -> ReviewEntry {
+        let dice = Dice::try_from([1, 2, 3, 4, 6]).unwrap();
+        ReviewEntry {
+            round: 1,
+            player_id,
+            dice,
+            remaining_rolls: 0,
+            chosen: Choice::Fill(Boxes::Chance),
+            best: Choice::Fill(Boxes::SmallStraight),
+            loss,
+        }
+    }
+
+    #[test]
+    fn accuracy_test() {
+        let review = Review {
+            num_players: 2,
+            entries: vec![entry(0, 0.0), entry(0, 2.5), entry(0, 1e-9), entry(0, 0.5)],
+        };
+        assert_eq!(review.get_accuracy(0), 0.5);
+        assert!((review.get_points_lost(0) - 3.0).abs() < 1e-6);
+        assert_eq!(review.get_accuracy(1), 1.0);
+        assert_eq!(review.get_points_lost(1), 0.0);
+    }
+
+    #[test]
+    fn replay_test() {
+        let table = ScoreTable::with_only_left(&[Boxes::LargeStraight, Boxes::Chance]);
+
+        /* Rerolls a five into a straight and scores it with a roll left */
+        let mut play = Play::rolled(&[2, 3, 4, 5, 5], 2);
+        play.get_mut_hand().hold(4, false).unwrap();
+        play.progress().unwrap();
+        play.enter_pip(1).unwrap();
+        play.progress().unwrap();
+        let straight = play.record_turn(Boxes::LargeStraight).unwrap();
+        let [hold] = straight.holds[..] else {
+            panic!("one hold is recorded");
+        };
+        assert_eq!(hold.dice, Dice::try_from([2, 3, 4, 5, 5]).unwrap());
+        assert_eq!(hold.held, Dice::try_from([2, 3, 4, 5]).unwrap());
+        assert_eq!(hold.remaining_rolls, 2);
+        assert_eq!(straight.dice, Dice::try_from([1, 2, 3, 4, 5]).unwrap());
+        assert_eq!(straight.remaining_rolls, 1);
+
+        /* Scores low dice in Chance without rerolling them */
+        let chance = Play::rolled(&[1, 1, 1, 1, 2], 2)
+            .record_turn(Boxes::Chance)
+            .unwrap();
+        assert!(chance.holds.is_empty());
+
+        let mut solver = Solver::new();
+        let review = Review::replay(
+            &mut solver,
+            vec![table.get_column(0).clone()],
+            &[straight, chance],
+        )
+        .unwrap();
+        let entries = review.get_entries();
+        let choices: Vec<_> = entries
+            .iter()
+            .map(|e| (e.round, e.remaining_rolls, e.chosen))
+            .collect();
+        let hold = |dice: Result<Dice>| Choice::Hold(dice.unwrap());
+        assert!(
+            choices
+                == [
+                    (12, 2, hold(Dice::try_from([2, 3, 4, 5]))),
+                    (12, 1, hold(Dice::try_from([1, 2, 3, 4, 5]))),
+                    (12, 1, Choice::Fill(Boxes::LargeStraight)),
+                    (13, 2, hold(Dice::try_from([1, 1, 1, 1, 2]))),
+                    (13, 2, Choice::Fill(Boxes::Chance)),
+                ]
+        );
+        assert!(entries[..3].iter().all(|e| e.is_best()));
+        assert!(entries[3].best == Choice::Hold(Dice::default()));
+        /* Two more rolls of five dice for Chance are worth 5 * 4.25 against the 6 points scored */
+        assert!((entries[3].loss - 15.25).abs() < 1e-9);
+        assert!(entries[4].is_best());
+        assert_eq!(review.get_accuracy(0), 0.8);
+    }
+}
@@ -0,0 +1,1308 @@
+use crate::dice::Dice;
+use crate::hand::{Die, Hand};
+use crate::play::TurnContext;
+use crate::score_lookup::ScoreLookup;
+use crate::score_table::{ScoreColumn, ScoreTable};
+use crate::scoring::{
+    chance, contains_pips, groups_of, has_straight, highest_n_of_a_kind, is_full_house, is_yahtzee,
+    m_and_n_of_a_kind, n_of_a_kind, two_pairs, upper_section_scoring, Boxes,
+};
+use enum_iterator::Sequence;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(PartialEq, Eq, Sequence, Clone, Copy)]
+pub enum Variant {
+    Yahtzee,
+    Yatzy,
+    MaxiYatzy,
+    TripleYahtzee,
+    Yamb,
+    Generala,
+    Yacht,
+    Balut,
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Variant::Yahtzee => f.pad("Yahtzee"),
+            Variant::Yatzy => f.pad("Yatzy"),
+            Variant::MaxiYatzy => f.pad("Maxi Yatzy"),
+            Variant::TripleYahtzee => f.pad("Triple Yahtzee"),
+            Variant::Yamb => f.pad("Yamb"),
+            Variant::Generala => f.pad("Generala"),
+            Variant::Yacht => f.pad("Yacht (1950s)"),
+            Variant::Balut => f.pad("Balut"),
+        }
+    }
+}
+
+impl Variant {
+    pub fn rule_set(&self) -> Rc<dyn RuleSet> {
+        match self {
+            Variant::Yahtzee => Rc::new(ClassicYahtzee),
+            Variant::Yatzy => Rc::new(Yatzy),
+            Variant::MaxiYatzy => Rc::new(MaxiYatzy),
+            Variant::TripleYahtzee => Rc::new(TripleYahtzee),
+            Variant::Yamb => Rc::new(Yamb),
+            Variant::Generala => Rc::new(Generala),
+            Variant::Yacht => Rc::new(Yacht),
+            Variant::Balut => Rc::new(Balut),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct UpperBonus {
+    pub targets: &'static [(Boxes, u32)],
+    pub threshold: u32,
+    pub point: u32,
+}
+
+#[derive(Clone, Copy)]
+pub enum AwardCondition {
+    AtLeast(u32),
+    AllScored,
+    EachScored,
+}
+
+#[derive(Clone, Copy)]
+pub struct CategoryAward {
+    pub target: Boxes,
+    pub condition: AwardCondition,
+    pub point: u32,
+}
+
+/* The order in which the boxes of a column must be filled */
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum FillOrder {
+    Free,
+    Down,
+    Up,
+    Announce,
+}
+
+#[derive(Clone, Copy)]
+pub struct ColumnSpec {
+    pub name: &'static str,
+    pub multiplier: u32,
+    pub order: FillOrder,
+}
+
+impl ColumnSpec {
+    pub const SINGLE: [ColumnSpec; 1] = [ColumnSpec {
+        name: "",
+        multiplier: 1,
+        order: FillOrder::Free,
+    }];
+}
+
+pub trait RuleSet {
+    fn boxes(&self) -> &'static [Boxes];
+
+    fn score(&self, b: Boxes, dice: &Dice) -> u32;
+
+    fn variant(&self) -> Variant;
+
+    /* Scores of every box for every full hand, built on the first use */
+    fn lookup(&self) -> &'static ScoreLookup {
+        ScoreLookup::of(self.variant())
+    }
+
+    /* Same as score but in constant time unless the hand is partial */
+    fn look_up_score(&self, b: Boxes, dice: &Dice) -> u32 {
+        self.lookup()
+            .get(b, dice)
+            .unwrap_or_else(|| self.score(b, dice))
+    }
+
+    fn dice_num(&self) -> usize {
+        Hand::DEFAULT_DICE_NUM
+    }
+
+    /* Score columns of a player's sheet; each column's total is multiplied by its multiplier. */
+    fn columns(&self) -> &'static [ColumnSpec] {
+        &ColumnSpec::SINGLE
+    }
+
+    /* Whether the rolls left unused in a turn are carried over to the next turn. */
+    fn saves_unused_rolls(&self) -> bool {
+        false
+    }
+
+    fn box_name(&self, b: Boxes) -> String {
+        b.to_string()
+    }
+
+    fn entries_per_box(&self) -> usize {
+        1
+    }
+
+    /* Players are ranked by points instead of the score if there are any awards. */
+    fn category_awards(&self) -> &'static [CategoryAward] {
+        &[]
+    }
+
+    fn points_for_total(&self, _total: u32) -> i32 {
+        0
+    }
+
+    fn upper_bonus(&self) -> Option<UpperBonus> {
+        None
+    }
+
+    fn yahtzee_bonus_point(&self) -> Option<u32> {
+        None
+    }
+
+    fn is_joker(&self, _column: &ScoreColumn, _dice: &Dice) -> bool {
+        false
+    }
+
+    fn can_fill(&self, column: &ScoreColumn, b: Boxes, _dice: &Dice) -> bool {
+        !column.has_score_in(b)
+    }
+
+    fn score_in(&self, _column: &ScoreColumn, b: Boxes, dice: &Dice, _turn: &TurnContext) -> u32 {
+        self.look_up_score(b, dice)
+    }
+
+    /* Whether filling a box with the dice ends the game with the player as the winner */
+    fn wins_outright(&self, _dice: &Dice, _turn: &TurnContext) -> bool {
+        false
+    }
+
+    /* The sum of the boxes counted in the total of a column, without bonuses */
+    fn boxes_total(&self, column: &ScoreColumn) -> u32 {
+        self.boxes()
+            .iter()
+            .map(|&b| column.get_score(b).unwrap_or(0))
+            .sum()
+    }
+
+    fn is_game_over(&self, tables: &[ScoreTable]) -> bool {
+        tables.iter().any(|st| st.has_won_outright()) || tables.iter().all(|st| st.has_all_scores())
+    }
+}
+
+pub struct ClassicYahtzee;
+
+impl ClassicYahtzee {
+    const BOXES: [Boxes; 13] = [
+        Boxes::Aces,
+        Boxes::Twos,
+        Boxes::Threes,
+        Boxes::Fours,
+        Boxes::Fives,
+        Boxes::Sixes,
+        Boxes::ThreeOfaAKind,
+        Boxes::FourOfaAKind,
+        Boxes::FullHouse,
+        Boxes::SmallStraight,
+        Boxes::LargeStraight,
+        Boxes::Yahtzee,
+        Boxes::Chance,
+    ];
+    pub const FULL_HOUSE_SCORE: u32 = 25;
+    pub const SMALL_STRAIGHT_SCORE: u32 = 30;
+    pub const LARGE_STRAIGHT_SCORE: u32 = 40;
+    pub const YAHTZEE_SCORE: u32 = 50;
+    pub const BONUS_TARGETS: [(Boxes, u32); 6] = [
+        (Boxes::Aces, 1),
+        (Boxes::Twos, 2),
+        (Boxes::Threes, 3),
+        (Boxes::Fours, 4),
+        (Boxes::Fives, 5),
+        (Boxes::Sixes, 6),
+    ];
+    pub const BONUS_THRESHOLD: u32 = {
+        /* This means (1 + 2 + 3 + 4 + 5 + 6) * 3. */
+        let mut sum = 0;
+        let mut i = 1;
+        while i <= Die::PIPS.len() {
+            sum += i;
+            i += 1;
+        }
+        (sum * 3) as u32
+    };
+    pub const BONUS_POINT: u32 = 35;
+    pub const YAHTZEE_BONUS_POINT: u32 = 100;
+
+    fn is_upper(b: Boxes) -> bool {
+        Self::BONUS_TARGETS.iter().any(|&(ub, ..)| ub == b)
+    }
+}
+
+impl RuleSet for ClassicYahtzee {
+    fn boxes(&self) -> &'static [Boxes] {
+        &Self::BOXES
+    }
+
+    fn score(&self, b: Boxes, dice: &Dice) -> u32 {
+        let bool_score = |cond: bool, score: u32| if cond { score } else { 0 };
+        match b {
+            Boxes::Aces => upper_section_scoring(dice, 1),
+            Boxes::Twos => upper_section_scoring(dice, 2),
+            Boxes::Threes => upper_section_scoring(dice, 3),
+            Boxes::Fours => upper_section_scoring(dice, 4),
+            Boxes::Fives => upper_section_scoring(dice, 5),
+            Boxes::Sixes => upper_section_scoring(dice, 6),
+            Boxes::ThreeOfaAKind => n_of_a_kind(dice, 3),
+            Boxes::FourOfaAKind => n_of_a_kind(dice, 4),
+            Boxes::FullHouse => bool_score(is_full_house(dice), Self::FULL_HOUSE_SCORE),
+            Boxes::SmallStraight => bool_score(has_straight(dice, 4), Self::SMALL_STRAIGHT_SCORE),
+            Boxes::LargeStraight => bool_score(has_straight(dice, 5), Self::LARGE_STRAIGHT_SCORE),
+            Boxes::Yahtzee => bool_score(is_yahtzee(dice, self.dice_num()), Self::YAHTZEE_SCORE),
+            Boxes::Chance => chance(dice),
+            _ => 0,
+        }
+    }
+
+    fn variant(&self) -> Variant {
+        Variant::Yahtzee
+    }
+
+    fn upper_bonus(&self) -> Option<UpperBonus> {
+        Some(UpperBonus {
+            targets: &Self::BONUS_TARGETS,
+            threshold: Self::BONUS_THRESHOLD,
+            point: Self::BONUS_POINT,
+        })
+    }
+
+    fn yahtzee_bonus_point(&self) -> Option<u32> {
+        Some(Self::YAHTZEE_BONUS_POINT)
+    }
+
+    /* A Yahtzee rolled after the Yahtzee box holds 50 earns a bonus chip and is a Joker. */
+    fn is_joker(&self, column: &ScoreColumn, dice: &Dice) -> bool {
+        is_yahtzee(dice, self.dice_num())
+            && column.get_score(Boxes::Yahtzee) == Some(Self::YAHTZEE_SCORE)
+    }
+
+    fn can_fill(&self, column: &ScoreColumn, b: Boxes, dice: &Dice) -> bool {
+        if column.has_score_in(b) {
+            return false;
+        }
+        if !self.is_joker(column, dice) {
+            return true;
+        }
+
+        /* Joker rules: the matching upper box first, then any lower box, then any upper box.
+         * Faces above six, such as those of a d8, have no matching upper box. */
+        let pip = dice.iter().next().map(|p| p.value());
+        let forced = Self::BONUS_TARGETS
+            .iter()
+            .find(|&&(.., p)| Some(p) == pip)
+            .map(|&(ub, ..)| ub);
+        if let Some(forced) = forced.filter(|&ub| !column.has_score_in(ub)) {
+            return b == forced;
+        }
+
+        let has_open_lower = Self::BOXES
+            .iter()
+            .any(|&lb| !Self::is_upper(lb) && !column.has_score_in(lb));
+        Self::is_upper(b) != has_open_lower
+    }
+
+    /* Full house and straights score their full value when a Yahtzee is used as a Joker. */
+    fn score_in(&self, column: &ScoreColumn, b: Boxes, dice: &Dice, _turn: &TurnContext) -> u32 {
+        if !self.is_joker(column, dice) {
+            return self.look_up_score(b, dice);
+        }
+
+        match b {
+            Boxes::FullHouse => Self::FULL_HOUSE_SCORE,
+            Boxes::SmallStraight => Self::SMALL_STRAIGHT_SCORE,
+            Boxes::LargeStraight => Self::LARGE_STRAIGHT_SCORE,
+            _ => self.look_up_score(b, dice),
+        }
+    }
+}
+
+pub struct Yatzy;
+
+impl Yatzy {
+    const BOXES: [Boxes; 15] = [
+        Boxes::Aces,
+        Boxes::Twos,
+        Boxes::Threes,
+        Boxes::Fours,
+        Boxes::Fives,
+        Boxes::Sixes,
+        Boxes::OnePair,
+        Boxes::TwoPairs,
+        Boxes::ThreeOfaAKind,
+        Boxes::FourOfaAKind,
+        Boxes::SmallStraight,
+        Boxes::LargeStraight,
+        Boxes::FullHouse,
+        Boxes::Chance,
+        Boxes::Yahtzee,
+    ];
+    pub const SMALL_STRAIGHT: [u32; 5] = [1, 2, 3, 4, 5];
+    pub const LARGE_STRAIGHT: [u32; 5] = [2, 3, 4, 5, 6];
+    pub const YATZY_SCORE: u32 = 50;
+    pub const BONUS_THRESHOLD: u32 = ClassicYahtzee::BONUS_THRESHOLD;
+    pub const BONUS_POINT: u32 = 50;
+}
+
+impl RuleSet for Yatzy {
+    fn boxes(&self) -> &'static [Boxes] {
+        &Self::BOXES
+    }
+
+    fn score(&self, b: Boxes, dice: &Dice) -> u32 {
+        let of_a_kind = |n: usize| highest_n_of_a_kind(dice, n).map_or(0, |p| p * (n as u32));
+        let straight = |pips: &[u32]| {
+            if contains_pips(dice, pips) {
+                pips.iter().sum()
+            } else {
+                0
+            }
+        };
+        match b {
+            Boxes::Aces => upper_section_scoring(dice, 1),
+            Boxes::Twos => upper_section_scoring(dice, 2),
+            Boxes::Threes => upper_section_scoring(dice, 3),
+            Boxes::Fours => upper_section_scoring(dice, 4),
+            Boxes::Fives => upper_section_scoring(dice, 5),
+            Boxes::Sixes => upper_section_scoring(dice, 6),
+            Boxes::OnePair => of_a_kind(2),
+            Boxes::TwoPairs => two_pairs(dice).map_or(0, |(h, l)| (h + l) * 2),
+            Boxes::ThreeOfaAKind => of_a_kind(3),
+            Boxes::FourOfaAKind => of_a_kind(4),
+            Boxes::SmallStraight => straight(&Self::SMALL_STRAIGHT),
+            Boxes::LargeStraight => straight(&Self::LARGE_STRAIGHT),
+            Boxes::FullHouse if is_full_house(dice) => chance(dice),
+            Boxes::Chance => chance(dice),
+            Boxes::Yahtzee if is_yahtzee(dice, self.dice_num()) => Self::YATZY_SCORE,
+            _ => 0,
+        }
+    }
+
+    fn variant(&self) -> Variant {
+        Variant::Yatzy
+    }
+
+    fn box_name(&self, b: Boxes) -> String {
+        match b {
+            Boxes::Aces => String::from("Ones"),
+            Boxes::Yahtzee => String::from("Yatzy"),
+            _ => b.to_string(),
+        }
+    }
+
+    fn upper_bonus(&self) -> Option<UpperBonus> {
+        Some(UpperBonus {
+            targets: &ClassicYahtzee::BONUS_TARGETS,
+            threshold: Self::BONUS_THRESHOLD,
+            point: Self::BONUS_POINT,
+        })
+    }
+}
+
+pub struct MaxiYatzy;
+
+impl MaxiYatzy {
+    const BOXES: [Boxes; 20] = [
+        Boxes::Aces,
+        Boxes::Twos,
+        Boxes::Threes,
+        Boxes::Fours,
+        Boxes::Fives,
+        Boxes::Sixes,
+        Boxes::OnePair,
+        Boxes::TwoPairs,
+        Boxes::ThreePairs,
+        Boxes::ThreeOfaAKind,
+        Boxes::FourOfaAKind,
+        Boxes::FiveOfaAKind,
+        Boxes::SmallStraight,
+        Boxes::LargeStraight,
+        Boxes::FullStraight,
+        Boxes::FullHouse,
+        Boxes::Castle,
+        Boxes::Tower,
+        Boxes::Chance,
+        Boxes::Yahtzee,
+    ];
+    pub const DICE_NUM: usize = 6;
+    pub const FULL_STRAIGHT: [u32; 6] = [1, 2, 3, 4, 5, 6];
+    pub const MAXI_YATZY_SCORE: u32 = 100;
+    pub const BONUS_THRESHOLD: u32 = ClassicYahtzee::BONUS_THRESHOLD / 3 * 4;
+    pub const BONUS_POINT: u32 = 50;
+}
+
+impl RuleSet for MaxiYatzy {
+    fn boxes(&self) -> &'static [Boxes] {
+        &Self::BOXES
+    }
+
+    fn score(&self, b: Boxes, dice: &Dice) -> u32 {
+        let of_a_kind = |n: usize| highest_n_of_a_kind(dice, n).map_or(0, |p| p * (n as u32));
+        let straight = |pips: &[u32]| {
+            if contains_pips(dice, pips) {
+                pips.iter().sum()
+            } else {
+                0
+            }
+        };
+        let m_and_n = |m: usize, n: usize| {
+            m_and_n_of_a_kind(dice, m, n).map_or(0, |(h, l)| h * (m as u32) + l * (n as u32))
+        };
+        match b {
+            Boxes::Aces => upper_section_scoring(dice, 1),
+            Boxes::Twos => upper_section_scoring(dice, 2),
+            Boxes::Threes => upper_section_scoring(dice, 3),
+            Boxes::Fours => upper_section_scoring(dice, 4),
+            Boxes::Fives => upper_section_scoring(dice, 5),
+            Boxes::Sixes => upper_section_scoring(dice, 6),
+            Boxes::OnePair => of_a_kind(2),
+            Boxes::TwoPairs => two_pairs(dice).map_or(0, |(h, l)| (h + l) * 2),
+            Boxes::ThreePairs => match groups_of(dice, 2)[..] {
+                [p0, p1, p2, ..] => (p0 + p1 + p2) * 2,
+                _ => 0,
+            },
+            Boxes::ThreeOfaAKind => of_a_kind(3),
+            Boxes::FourOfaAKind => of_a_kind(4),
+            Boxes::FiveOfaAKind => of_a_kind(5),
+            Boxes::SmallStraight => straight(&Yatzy::SMALL_STRAIGHT),
+            Boxes::LargeStraight => straight(&Yatzy::LARGE_STRAIGHT),
+            Boxes::FullStraight => straight(&Self::FULL_STRAIGHT),
+            Boxes::FullHouse => m_and_n(3, 2),
+            Boxes::Castle => m_and_n(3, 3),
+            Boxes::Tower => m_and_n(4, 2),
+            Boxes::Chance => chance(dice),
+            Boxes::Yahtzee if is_yahtzee(dice, self.dice_num()) => Self::MAXI_YATZY_SCORE,
+            _ => 0,
+        }
+    }
+
+    fn variant(&self) -> Variant {
+        Variant::MaxiYatzy
+    }
+
+    fn dice_num(&self) -> usize {
+        Self::DICE_NUM
+    }
+
+    fn saves_unused_rolls(&self) -> bool {
+        true
+    }
+
+    fn box_name(&self, b: Boxes) -> String {
+        match b {
+            Boxes::Aces => String::from("Ones"),
+            Boxes::Castle => String::from("Castle (Villa)"),
+            Boxes::Yahtzee => String::from("Maxi Yatzy"),
+            _ => b.to_string(),
+        }
+    }
+
+    fn upper_bonus(&self) -> Option<UpperBonus> {
+        Some(UpperBonus {
+            targets: &ClassicYahtzee::BONUS_TARGETS,
+            threshold: Self::BONUS_THRESHOLD,
+            point: Self::BONUS_POINT,
+        })
+    }
+}
+
+pub struct TripleYahtzee;
+
+impl TripleYahtzee {
+    pub const COLUMNS: [ColumnSpec; 3] = [
+        ColumnSpec {
+            name: "x1",
+            multiplier: 1,
+            order: FillOrder::Free,
+        },
+        ColumnSpec {
+            name: "x2",
+            multiplier: 2,
+            order: FillOrder::Free,
+        },
+        ColumnSpec {
+            name: "x3",
+            multiplier: 3,
+            order: FillOrder::Free,
+        },
+    ];
+}
+
+impl RuleSet for TripleYahtzee {
+    fn boxes(&self) -> &'static [Boxes] {
+        ClassicYahtzee.boxes()
+    }
+
+    fn score(&self, b: Boxes, dice: &Dice) -> u32 {
+        ClassicYahtzee.score(b, dice)
+    }
+
+    fn variant(&self) -> Variant {
+        Variant::TripleYahtzee
+    }
+
+    fn columns(&self) -> &'static [ColumnSpec] {
+        &Self::COLUMNS
+    }
+
+    fn upper_bonus(&self) -> Option<UpperBonus> {
+        ClassicYahtzee.upper_bonus()
+    }
+
+    fn yahtzee_bonus_point(&self) -> Option<u32> {
+        ClassicYahtzee.yahtzee_bonus_point()
+    }
+
+    fn is_joker(&self, column: &ScoreColumn, dice: &Dice) -> bool {
+        ClassicYahtzee.is_joker(column, dice)
+    }
+
+    fn can_fill(&self, column: &ScoreColumn, b: Boxes, dice: &Dice) -> bool {
+        ClassicYahtzee.can_fill(column, b, dice)
+    }
+
+    fn score_in(&self, column: &ScoreColumn, b: Boxes, dice: &Dice, turn: &TurnContext) -> u32 {
+        ClassicYahtzee.score_in(column, b, dice, turn)
+    }
+}
+
+pub struct Yamb;
+
+impl Yamb {
+    const BOXES: [Boxes; 13] = [
+        Boxes::Aces,
+        Boxes::Twos,
+        Boxes::Threes,
+        Boxes::Fours,
+        Boxes::Fives,
+        Boxes::Sixes,
+        Boxes::Max,
+        Boxes::Min,
+        Boxes::ThreeOfaAKind,
+        Boxes::LargeStraight,
+        Boxes::FullHouse,
+        Boxes::FourOfaAKind,
+        Boxes::Yahtzee,
+    ];
+    pub const COLUMNS: [ColumnSpec; 4] = [
+        ColumnSpec {
+            name: "Down",
+            multiplier: 1,
+            order: FillOrder::Down,
+        },
+        ColumnSpec {
+            name: "Up",
+            multiplier: 1,
+            order: FillOrder::Up,
+        },
+        ColumnSpec {
+            name: "Free",
+            multiplier: 1,
+            order: FillOrder::Free,
+        },
+        ColumnSpec {
+            name: "Ann.",
+            multiplier: 1,
+            order: FillOrder::Announce,
+        },
+    ];
+    pub const TRIPS_POINT: u32 = 10;
+    pub const SMALL_STRAIGHT_SCORE: u32 = 35;
+    pub const LARGE_STRAIGHT_SCORE: u32 = 45;
+    pub const FULL_HOUSE_POINT: u32 = 30;
+    pub const POKER_POINT: u32 = 40;
+    pub const YAMB_POINT: u32 = 50;
+    pub const BONUS_THRESHOLD: u32 = 60;
+    pub const BONUS_POINT: u32 = 30;
+}
+
+impl RuleSet for Yamb {
+    fn boxes(&self) -> &'static [Boxes] {
+        &Self::BOXES
+    }
+
+    fn score(&self, b: Boxes, dice: &Dice) -> u32 {
+        let of_a_kind = |n: usize, point: u32| {
+            highest_n_of_a_kind(dice, n).map_or(0, |p| p * (n as u32) + point)
+        };
+        match b {
+            Boxes::Aces => upper_section_scoring(dice, 1),
+            Boxes::Twos => upper_section_scoring(dice, 2),
+            Boxes::Threes => upper_section_scoring(dice, 3),
+            Boxes::Fours => upper_section_scoring(dice, 4),
+            Boxes::Fives => upper_section_scoring(dice, 5),
+            Boxes::Sixes => upper_section_scoring(dice, 6),
+            Boxes::Max | Boxes::Min => chance(dice),
+            Boxes::ThreeOfaAKind => of_a_kind(3, Self::TRIPS_POINT),
+            Boxes::LargeStraight if contains_pips(dice, &Yatzy::LARGE_STRAIGHT) => {
+                Self::LARGE_STRAIGHT_SCORE
+            }
+            Boxes::LargeStraight if contains_pips(dice, &Yatzy::SMALL_STRAIGHT) => {
+                Self::SMALL_STRAIGHT_SCORE
+            }
+            Boxes::FullHouse if is_full_house(dice) => chance(dice) + Self::FULL_HOUSE_POINT,
+            Boxes::FourOfaAKind => of_a_kind(4, Self::POKER_POINT),
+            Boxes::Yahtzee => of_a_kind(5, Self::YAMB_POINT),
+            _ => 0,
+        }
+    }
+
+    fn variant(&self) -> Variant {
+        Variant::Yamb
+    }
+
+    fn columns(&self) -> &'static [ColumnSpec] {
+        &Self::COLUMNS
+    }
+
+    fn box_name(&self, b: Boxes) -> String {
+        match b {
+            Boxes::Aces => String::from("Ones"),
+            Boxes::ThreeOfaAKind => String::from("Trips"),
+            Boxes::LargeStraight => String::from("Straight"),
+            Boxes::FullHouse => String::from("Full"),
+            Boxes::FourOfaAKind => String::from("Poker"),
+            Boxes::Yahtzee => String::from("Yamb"),
+            _ => b.to_string(),
+        }
+    }
+
+    fn upper_bonus(&self) -> Option<UpperBonus> {
+        Some(UpperBonus {
+            targets: &ClassicYahtzee::BONUS_TARGETS,
+            threshold: Self::BONUS_THRESHOLD,
+            point: Self::BONUS_POINT,
+        })
+    }
+
+    /* Max and Min count only as their difference multiplied by Ones. */
+    fn boxes_total(&self, column: &ScoreColumn) -> u32 {
+        let score = |b: Boxes| column.get_score(b).unwrap_or(0);
+        let middle = match (
+            column.get_score(Boxes::Max),
+            column.get_score(Boxes::Min),
+            column.get_score(Boxes::Aces),
+        ) {
+            (Some(max), Some(min), Some(ones)) => max.saturating_sub(min) * ones,
+            _ => 0,
+        };
+        self.boxes()
+            .iter()
+            .filter(|&&b| b != Boxes::Max && b != Boxes::Min)
+            .map(|&b| score(b))
+            .sum::<u32>()
+            + middle
+    }
+}
+
+pub struct Generala;
+
+impl Generala {
+    const BOXES: [Boxes; 11] = [
+        Boxes::Aces,
+        Boxes::Twos,
+        Boxes::Threes,
+        Boxes::Fours,
+        Boxes::Fives,
+        Boxes::Sixes,
+        Boxes::LargeStraight,
+        Boxes::FullHouse,
+        Boxes::FourOfaAKind,
+        Boxes::Yahtzee,
+        Boxes::DoubleYahtzee,
+    ];
+    pub const STRAIGHTS: [[u32; 5]; 3] = [[1, 2, 3, 4, 5], [2, 3, 4, 5, 6], [1, 3, 4, 5, 6]];
+    pub const STRAIGHT_SCORE: u32 = 20;
+    pub const FULL_HOUSE_SCORE: u32 = 30;
+    pub const POKER_SCORE: u32 = 40;
+    pub const SERVED_BONUS: u32 = 5;
+    pub const GENERALA_SCORE: u32 = 50;
+    pub const DOUBLE_GENERALA_SCORE: u32 = 100;
+}
+
+impl RuleSet for Generala {
+    fn boxes(&self) -> &'static [Boxes] {
+        &Self::BOXES
+    }
+
+    fn score(&self, b: Boxes, dice: &Dice) -> u32 {
+        let bool_score = |cond: bool, score: u32| if cond { score } else { 0 };
+        match b {
+            Boxes::Aces => upper_section_scoring(dice, 1),
+            Boxes::Twos => upper_section_scoring(dice, 2),
+            Boxes::Threes => upper_section_scoring(dice, 3),
+            Boxes::Fours => upper_section_scoring(dice, 4),
+            Boxes::Fives => upper_section_scoring(dice, 5),
+            Boxes::Sixes => upper_section_scoring(dice, 6),
+            Boxes::LargeStraight => bool_score(
+                Self::STRAIGHTS.iter().any(|s| contains_pips(dice, s)),
+                Self::STRAIGHT_SCORE,
+            ),
+            Boxes::FullHouse => bool_score(is_full_house(dice), Self::FULL_HOUSE_SCORE),
+            Boxes::FourOfaAKind => bool_score(n_of_a_kind(dice, 4) > 0, Self::POKER_SCORE),
+            Boxes::Yahtzee => bool_score(is_yahtzee(dice, self.dice_num()), Self::GENERALA_SCORE),
+            Boxes::DoubleYahtzee => bool_score(
+                is_yahtzee(dice, self.dice_num()),
+                Self::DOUBLE_GENERALA_SCORE,
+            ),
+            _ => 0,
+        }
+    }
+
+    fn variant(&self) -> Variant {
+        Variant::Generala
+    }
+
+    fn box_name(&self, b: Boxes) -> String {
+        match b {
+            Boxes::Aces => String::from("Ones"),
+            Boxes::LargeStraight => String::from("Escalera"),
+            Boxes::FullHouse => String::from("Full"),
+            Boxes::FourOfaAKind => String::from("Poker"),
+            Boxes::Yahtzee => String::from("Generala"),
+            Boxes::DoubleYahtzee => String::from("Double Generala"),
+            _ => b.to_string(),
+        }
+    }
+
+    /* Served hands score extra, and a Double Generala counts only after a Generala. */
+    fn score_in(&self, column: &ScoreColumn, b: Boxes, dice: &Dice, turn: &TurnContext) -> u32 {
+        let score = self.look_up_score(b, dice);
+        match b {
+            Boxes::LargeStraight | Boxes::FullHouse | Boxes::FourOfaAKind
+                if score > 0 && turn.is_served() =>
+            {
+                score + Self::SERVED_BONUS
+            }
+            Boxes::DoubleYahtzee
+                if column.get_score(Boxes::Yahtzee) != Some(Self::GENERALA_SCORE) =>
+            {
+                0
+            }
+            _ => score,
+        }
+    }
+
+    fn wins_outright(&self, dice: &Dice, turn: &TurnContext) -> bool {
+        turn.is_served() && is_yahtzee(dice, self.dice_num())
+    }
+}
+
+/* The original rules of the 1950s, without any bonus */
+pub struct Yacht;
+
+impl Yacht {
+    const BOXES: [Boxes; 12] = [
+        Boxes::Aces,
+        Boxes::Twos,
+        Boxes::Threes,
+        Boxes::Fours,
+        Boxes::Fives,
+        Boxes::Sixes,
+        Boxes::FullHouse,
+        Boxes::FourOfaAKind,
+        Boxes::SmallStraight,
+        Boxes::LargeStraight,
+        Boxes::Chance,
+        Boxes::Yahtzee,
+    ];
+    pub const LITTLE_STRAIGHT: [u32; 5] = [1, 2, 3, 4, 5];
+    pub const BIG_STRAIGHT: [u32; 5] = [2, 3, 4, 5, 6];
+    pub const STRAIGHT_SCORE: u32 = 30;
+    pub const YACHT_SCORE: u32 = 50;
+}
+
+impl RuleSet for Yacht {
+    fn boxes(&self) -> &'static [Boxes] {
+        &Self::BOXES
+    }
+
+    fn score(&self, b: Boxes, dice: &Dice) -> u32 {
+        let bool_score = |cond: bool, score: u32| if cond { score } else { 0 };
+        match b {
+            Boxes::Aces => upper_section_scoring(dice, 1),
+            Boxes::Twos => upper_section_scoring(dice, 2),
+            Boxes::Threes => upper_section_scoring(dice, 3),
+            Boxes::Fours => upper_section_scoring(dice, 4),
+            Boxes::Fives => upper_section_scoring(dice, 5),
+            Boxes::Sixes => upper_section_scoring(dice, 6),
+            Boxes::FullHouse => bool_score(is_full_house(dice), chance(dice)),
+            Boxes::FourOfaAKind => highest_n_of_a_kind(dice, 4).map_or(0, |p| p * 4),
+            Boxes::SmallStraight => bool_score(
+                contains_pips(dice, &Self::LITTLE_STRAIGHT),
+                Self::STRAIGHT_SCORE,
+            ),
+            Boxes::LargeStraight => bool_score(
+                contains_pips(dice, &Self::BIG_STRAIGHT),
+                Self::STRAIGHT_SCORE,
+            ),
+            Boxes::Chance => chance(dice),
+            Boxes::Yahtzee => bool_score(is_yahtzee(dice, self.dice_num()), Self::YACHT_SCORE),
+            _ => 0,
+        }
+    }
+
+    fn variant(&self) -> Variant {
+        Variant::Yacht
+    }
+
+    fn box_name(&self, b: Boxes) -> String {
+        match b {
+            Boxes::Aces => String::from("Ones"),
+            Boxes::SmallStraight => String::from("Little straight"),
+            Boxes::LargeStraight => String::from("Big straight"),
+            Boxes::Chance => String::from("Choice"),
+            Boxes::Yahtzee => String::from("Yacht"),
+            _ => b.to_string(),
+        }
+    }
+}
+
+pub struct Balut;
+
+impl Balut {
+    const BOXES: [Boxes; 7] = [
+        Boxes::Fours,
+        Boxes::Fives,
+        Boxes::Sixes,
+        Boxes::LargeStraight,
+        Boxes::FullHouse,
+        Boxes::Chance,
+        Boxes::Yahtzee,
+    ];
+    pub const ENTRIES_PER_BOX: usize = 4;
+    pub const BALUT_POINT: u32 = 20;
+    pub const AWARDS: [CategoryAward; 7] = [
+        CategoryAward {
+            target: Boxes::Fours,
+            condition: AwardCondition::AtLeast(52),
+            point: 2,
+        },
+        CategoryAward {
+            target: Boxes::Fives,
+            condition: AwardCondition::AtLeast(65),
+            point: 2,
+        },
+        CategoryAward {
+            target: Boxes::Sixes,
+            condition: AwardCondition::AtLeast(78),
+            point: 2,
+        },
+        CategoryAward {
+            target: Boxes::LargeStraight,
+            condition: AwardCondition::AllScored,
+            point: 4,
+        },
+        CategoryAward {
+            target: Boxes::FullHouse,
+            condition: AwardCondition::AllScored,
+            point: 3,
+        },
+        CategoryAward {
+            target: Boxes::Chance,
+            condition: AwardCondition::AtLeast(100),
+            point: 2,
+        },
+        CategoryAward {
+            target: Boxes::Yahtzee,
+            condition: AwardCondition::EachScored,
+            point: 2,
+        },
+    ];
+    /* One point for every 50 points of the total above 350, and at least -2 points */
+    pub const TOTAL_POINTS_STEP: u32 = 50;
+    pub const TOTAL_POINTS_BASE: u32 = 350;
+    pub const MIN_TOTAL_POINTS: i32 = -2;
+}
+
+impl RuleSet for Balut {
+    fn boxes(&self) -> &'static [Boxes] {
+        &Self::BOXES
+    }
+
+    fn score(&self, b: Boxes, dice: &Dice) -> u32 {
+        let bool_score = |cond: bool, score: u32| if cond { score } else { 0 };
+        match b {
+            Boxes::Fours => upper_section_scoring(dice, 4),
+            Boxes::Fives => upper_section_scoring(dice, 5),
+            Boxes::Sixes => upper_section_scoring(dice, 6),
+            Boxes::LargeStraight => bool_score(
+                contains_pips(dice, &Yatzy::SMALL_STRAIGHT)
+                    || contains_pips(dice, &Yatzy::LARGE_STRAIGHT),
+                chance(dice),
+            ),
+            Boxes::FullHouse => bool_score(is_full_house(dice), chance(dice)),
+            Boxes::Chance => chance(dice),
+            Boxes::Yahtzee if is_yahtzee(dice, self.dice_num()) => Self::BALUT_POINT + chance(dice),
+            _ => 0,
+        }
+    }
+
+    fn variant(&self) -> Variant {
+        Variant::Balut
+    }
+
+    fn box_name(&self, b: Boxes) -> String {
+        match b {
+            Boxes::LargeStraight => String::from("Straight"),
+            Boxes::Chance => String::from("Choice"),
+            Boxes::Yahtzee => String::from("Balut"),
+            _ => b.to_string(),
+        }
+    }
+
+    fn entries_per_box(&self) -> usize {
+        Self::ENTRIES_PER_BOX
+    }
+
+    fn category_awards(&self) -> &'static [CategoryAward] {
+        &Self::AWARDS
+    }
+
+    fn points_for_total(&self, total: u32) -> i32 {
+        let steps = (total / Self::TOTAL_POINTS_STEP) as i32;
+        let base = (Self::TOTAL_POINTS_BASE / Self::TOTAL_POINTS_STEP) as i32;
+        (steps - base).max(Self::MIN_TOTAL_POINTS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::play::{Play, PlayPhase};
+    use crate::rule_set::*;
+
+    #[test]
+    fn classic_joker_scoring_test() {
+        let yahtzee = Dice::try_from([3, 3, 3, 3, 3]).unwrap();
+        let turn = TurnContext::new(Play::DEFAULT_ROLLS_PER_TURN);
+        let mut st = ScoreColumn::new();
+        assert!(!ClassicYahtzee.is_joker(&st, &yahtzee));
+        assert_eq!(
+            ClassicYahtzee.score_in(&st, Boxes::FullHouse, &yahtzee, &turn),
+            0
+        );
+
+        st.confirm_score(Boxes::Yahtzee, ClassicYahtzee::YAHTZEE_SCORE)
+            .unwrap();
+        assert!(ClassicYahtzee.is_joker(&st, &yahtzee));
+        for (b, score) in [
+            (Boxes::FullHouse, ClassicYahtzee::FULL_HOUSE_SCORE),
+            (Boxes::SmallStraight, ClassicYahtzee::SMALL_STRAIGHT_SCORE),
+            (Boxes::LargeStraight, ClassicYahtzee::LARGE_STRAIGHT_SCORE),
+            (Boxes::ThreeOfaAKind, 15),
+            (Boxes::Threes, 15),
+            (Boxes::Fours, 0),
+        ] {
+            assert_eq!(ClassicYahtzee.score_in(&st, b, &yahtzee, &turn), score);
+        }
+    }
+
+    #[test]
+    fn classic_joker_d8_test() {
+        let yahtzee = Dice::try_from([8, 8, 8, 8, 8]).unwrap();
+        let mut st = ScoreColumn::new();
+        st.confirm_score(Boxes::Yahtzee, ClassicYahtzee::YAHTZEE_SCORE)
+            .unwrap();
+        assert!(ClassicYahtzee.is_joker(&st, &yahtzee));
+
+        /* No upper box matches eights, so any open lower box may be filled */
+        assert!(ClassicYahtzee.can_fill(&st, Boxes::FullHouse, &yahtzee));
+        assert!(ClassicYahtzee.can_fill(&st, Boxes::Chance, &yahtzee));
+        assert!(!ClassicYahtzee.can_fill(&st, Boxes::Aces, &yahtzee));
+        assert!(TripleYahtzee.can_fill(&st, Boxes::Chance, &yahtzee));
+
+        for &b in ClassicYahtzee.boxes() {
+            if !ClassicYahtzee::is_upper(b) && b != Boxes::Yahtzee {
+                st.confirm_score(b, 0).unwrap();
+            }
+        }
+        assert!(ClassicYahtzee.can_fill(&st, Boxes::Sixes, &yahtzee));
+    }
+
+    #[test]
+    fn yatzy_scoring_test() {
+        let cases: [(Boxes, [u32; 5], u32); 16] = [
+            (Boxes::OnePair, [3, 3, 5, 5, 1], 10),
+            (Boxes::OnePair, [1, 2, 3, 4, 6], 0),
+            (Boxes::TwoPairs, [3, 3, 5, 5, 1], 16),
+            (Boxes::TwoPairs, [3, 3, 3, 3, 1], 0),
+            (Boxes::ThreeOfaAKind, [4, 4, 4, 4, 1], 12),
+            (Boxes::ThreeOfaAKind, [4, 4, 2, 2, 1], 0),
+            (Boxes::FourOfaAKind, [2, 2, 2, 2, 2], 8),
+            (Boxes::SmallStraight, [5, 4, 3, 2, 1], 15),
+            (Boxes::SmallStraight, [2, 3, 4, 5, 6], 0),
+            (Boxes::LargeStraight, [6, 5, 4, 3, 2], 20),
+            (Boxes::LargeStraight, [1, 2, 3, 4, 5], 0),
+            (Boxes::FullHouse, [6, 6, 2, 2, 2], 18),
+            (Boxes::FullHouse, [6, 6, 6, 6, 6], 0),
+            (Boxes::Chance, [6, 6, 2, 2, 1], 17),
+            (Boxes::Yahtzee, [6, 6, 6, 6, 6], Yatzy::YATZY_SCORE),
+            (Boxes::Yahtzee, [6, 6, 6, 6, 5], 0),
+        ];
+        for (b, dice, score) in cases {
+            assert_eq!(Yatzy.score(b, &Dice::try_from(dice).unwrap()), score);
+        }
+    }
+
+    #[test]
+    fn yatzy_bonus_test() {
+        let mut st = ScoreColumn::with_rule_set(Variant::Yatzy.rule_set());
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
+            st.confirm_score(b, p * 3).unwrap();
+        }
+        assert_eq!(st.calculate_bonus(), Some(Yatzy::BONUS_POINT));
+        assert_eq!(
+            st.get_total_score(),
+            Yatzy::BONUS_THRESHOLD + Yatzy::BONUS_POINT
+        );
+        assert!(!st.is_joker(&Dice::try_from([2, 2, 2, 2, 2]).unwrap()));
+    }
+
+    #[test]
+    fn maxi_yatzy_scoring_test() {
+        let cases: [(Boxes, [u32; 6], u32); 17] = [
+            (Boxes::OnePair, [3, 3, 5, 5, 1, 2], 10),
+            (Boxes::TwoPairs, [3, 3, 5, 5, 1, 1], 16),
+            (Boxes::ThreePairs, [3, 3, 5, 5, 1, 1], 18),
+            (Boxes::ThreePairs, [3, 3, 3, 3, 1, 1], 0),
+            (Boxes::FiveOfaAKind, [4, 4, 4, 4, 4, 1], 20),
+            (Boxes::SmallStraight, [5, 4, 3, 2, 1, 1], 15),
+            (Boxes::LargeStraight, [6, 5, 4, 3, 2, 2], 20),
+            (Boxes::FullStraight, [6, 5, 4, 3, 2, 1], 21),
+            (Boxes::FullStraight, [6, 5, 4, 3, 2, 2], 0),
+            (Boxes::FullHouse, [6, 6, 2, 2, 2, 1], 18),
+            (Boxes::FullHouse, [6, 6, 6, 6, 6, 6], 0),
+            (Boxes::Castle, [6, 6, 6, 2, 2, 2], 24),
+            (Boxes::Castle, [6, 6, 6, 2, 2, 1], 0),
+            (Boxes::Tower, [5, 5, 5, 5, 2, 2], 24),
+            (Boxes::Tower, [5, 5, 5, 2, 2, 2], 0),
+            (
+                Boxes::Yahtzee,
+                [1, 1, 1, 1, 1, 1],
+                MaxiYatzy::MAXI_YATZY_SCORE,
+            ),
+            (Boxes::Yahtzee, [1, 1, 1, 1, 1, 2], 0),
+        ];
+        for (b, dice, score) in cases {
+            assert_eq!(MaxiYatzy.score(b, &Dice::try_from(dice).unwrap()), score);
+        }
+    }
+
+    #[test]
+    fn maxi_yatzy_bonus_test() {
+        let mut st = ScoreColumn::with_rule_set(Variant::MaxiYatzy.rule_set());
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
+            st.confirm_score(b, p * 3).unwrap();
+        }
+        assert_eq!(st.calculate_bonus(), Some(0));
+
+        let mut st = ScoreColumn::with_rule_set(Variant::MaxiYatzy.rule_set());
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS[1..].iter() {
+            st.confirm_score(b, p * 4).unwrap();
+        }
+        assert_eq!(st.calculate_bonus(), None);
+        st.confirm_score(Boxes::Aces, 4).unwrap();
+        assert_eq!(st.get_total_upper_score(), MaxiYatzy::BONUS_THRESHOLD);
+        assert_eq!(st.calculate_bonus(), Some(MaxiYatzy::BONUS_POINT));
+    }
+
+    #[test]
+    fn triple_yahtzee_total_test() {
+        let rule = Variant::TripleYahtzee.rule_set();
+        let mut st = ScoreTable::with_rule_set(rule.clone());
+        let turn = TurnContext::new(Play::DEFAULT_ROLLS_PER_TURN);
+        for col in 0..st.get_num_columns() {
+            for &b in rule.boxes() {
+                st.confirm_dice(col, b, &Dice::try_from([6, 6, 6, 6, 6]).unwrap(), &turn)
+                    .unwrap();
+            }
+        }
+        assert!(rule.is_game_over(&[st.clone()]));
+        let column_total = st.get_column(0).get_total_score();
+        assert_eq!(st.get_column_total_score(2), column_total * 3);
+        assert_eq!(st.get_total_score(), column_total * 6);
+    }
+
+    #[test]
+    fn yamb_scoring_test() {
+        let cases: [(Boxes, [u32; 5], u32); 10] = [
+            (Boxes::ThreeOfaAKind, [4, 4, 4, 4, 1], 22),
+            (Boxes::ThreeOfaAKind, [4, 4, 2, 2, 1], 0),
+            (
+                Boxes::LargeStraight,
+                [5, 4, 3, 2, 1],
+                Yamb::SMALL_STRAIGHT_SCORE,
+            ),
+            (
+                Boxes::LargeStraight,
+                [2, 3, 4, 5, 6],
+                Yamb::LARGE_STRAIGHT_SCORE,
+            ),
+            (Boxes::LargeStraight, [1, 3, 4, 5, 6], 0),
+            (Boxes::FullHouse, [6, 6, 2, 2, 2], 48),
+            (Boxes::FourOfaAKind, [3, 3, 3, 3, 6], 52),
+            (Boxes::Yahtzee, [6, 6, 6, 6, 6], 80),
+            (Boxes::Yahtzee, [6, 6, 6, 6, 5], 0),
+            (Boxes::Min, [6, 6, 2, 2, 1], 17),
+        ];
+        for (b, dice, score) in cases {
+            assert_eq!(Yamb.score(b, &Dice::try_from(dice).unwrap()), score);
+        }
+    }
+
+    #[test]
+    fn yamb_middle_section_test() {
+        let mut st = ScoreColumn::with_rule_set(Variant::Yamb.rule_set());
+        st.confirm_score(Boxes::Max, 27).unwrap();
+        st.confirm_score(Boxes::Min, 9).unwrap();
+        assert_eq!(st.get_total_score(), 0);
+        st.confirm_score(Boxes::Aces, 3).unwrap();
+        assert_eq!(st.get_total_score(), 3 + (27 - 9) * 3);
+    }
+
+    #[test]
+    fn generala_served_test() {
+        let served = TurnContext::new(PlayPhase::INIT_ROLL_COUNT);
+        let later = TurnContext::new(Play::DEFAULT_ROLLS_PER_TURN);
+        let mut st = ScoreColumn::with_rule_set(Variant::Generala.rule_set());
+        for (b, dice, served_score, later_score) in [
+            (Boxes::LargeStraight, [1, 3, 4, 5, 6], 25, 20),
+            (Boxes::FullHouse, [2, 2, 5, 5, 5], 35, 30),
+            (Boxes::FourOfaAKind, [4, 4, 4, 4, 1], 45, 40),
+            (Boxes::FourOfaAKind, [4, 4, 4, 2, 1], 0, 0),
+            (Boxes::Yahtzee, [6, 6, 6, 6, 6], 50, 50),
+            (Boxes::DoubleYahtzee, [6, 6, 6, 6, 6], 0, 0),
+        ] {
+            assert_eq!(
+                Generala.score_in(&st, b, &Dice::try_from(dice).unwrap(), &served),
+                served_score
+            );
+            assert_eq!(
+                Generala.score_in(&st, b, &Dice::try_from(dice).unwrap(), &later),
+                later_score
+            );
+        }
+
+        st.confirm_score(Boxes::Yahtzee, Generala::GENERALA_SCORE)
+            .unwrap();
+        assert_eq!(
+            Generala.score_in(
+                &st,
+                Boxes::DoubleYahtzee,
+                &Dice::try_from([2, 2, 2, 2, 2]).unwrap(),
+                &later
+            ),
+            Generala::DOUBLE_GENERALA_SCORE
+        );
+        assert!(Generala.wins_outright(&Dice::try_from([2, 2, 2, 2, 2]).unwrap(), &served));
+        assert!(!Generala.wins_outright(&Dice::try_from([2, 2, 2, 2, 2]).unwrap(), &later));
+    }
+
+    #[test]
+    fn generala_wins_outright_test() {
+        let rule = Variant::Generala.rule_set();
+        let mut tables = vec![
+            ScoreTable::with_rule_set(rule.clone()),
+            ScoreTable::with_rule_set(rule.clone()),
+        ];
+        tables[0]
+            .confirm_dice(
+                0,
+                Boxes::Yahtzee,
+                &Dice::try_from([3, 3, 3, 3, 3]).unwrap(),
+                &TurnContext::new(2),
+            )
+            .unwrap();
+        assert!(!rule.is_game_over(&tables));
+        tables[1]
+            .confirm_dice(
+                0,
+                Boxes::Aces,
+                &Dice::try_from([3, 3, 3, 3, 3]).unwrap(),
+                &TurnContext::new(1),
+            )
+            .unwrap();
+        assert!(tables[1].has_won_outright());
+        assert!(rule.is_game_over(&tables));
+    }
+
+    #[test]
+    fn yacht_scoring_test() {
+        let cases: [(Boxes, [u32; 5], u32); 10] = [
+            (Boxes::FullHouse, [6, 6, 2, 2, 2], 18),
+            (Boxes::FullHouse, [6, 6, 6, 6, 6], 0),
+            (Boxes::FourOfaAKind, [5, 5, 5, 5, 1], 20),
+            (Boxes::FourOfaAKind, [5, 5, 5, 5, 5], 20),
+            (Boxes::FourOfaAKind, [5, 5, 5, 1, 1], 0),
+            (Boxes::SmallStraight, [5, 4, 3, 2, 1], Yacht::STRAIGHT_SCORE),
+            (Boxes::SmallStraight, [2, 3, 4, 5, 6], 0),
+            (Boxes::LargeStraight, [2, 3, 4, 5, 6], Yacht::STRAIGHT_SCORE),
+            (Boxes::LargeStraight, [1, 2, 3, 4, 5], 0),
+            (Boxes::Yahtzee, [1, 1, 1, 1, 1], Yacht::YACHT_SCORE),
+        ];
+        for (b, dice, score) in cases {
+            assert_eq!(Yacht.score(b, &Dice::try_from(dice).unwrap()), score);
+        }
+    }
+
+    #[test]
+    fn yacht_no_bonus_test() {
+        let mut st = ScoreColumn::with_rule_set(Variant::Yacht.rule_set());
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
+            st.confirm_score(b, p * 4).unwrap();
+        }
+        assert_eq!(st.calculate_bonus(), Some(0));
+        assert_eq!(st.get_total_score(), 84);
+    }
+
+    #[test]
+    fn balut_scoring_test() {
+        let cases: [(Boxes, [u32; 5], u32); 7] = [
+            (Boxes::Fours, [4, 4, 4, 2, 1], 12),
+            (Boxes::LargeStraight, [5, 4, 3, 2, 1], 15),
+            (Boxes::LargeStraight, [6, 4, 3, 2, 5], 20),
+            (Boxes::LargeStraight, [6, 4, 3, 2, 2], 0),
+            (Boxes::FullHouse, [6, 6, 2, 2, 2], 18),
+            (Boxes::Yahtzee, [6, 6, 6, 6, 6], 50),
+            (Boxes::Yahtzee, [6, 6, 6, 6, 1], 0),
+        ];
+        for (b, dice, score) in cases {
+            assert_eq!(Balut.score(b, &Dice::try_from(dice).unwrap()), score);
+        }
+    }
+
+    #[test]
+    fn balut_points_test() {
+        let mut st = ScoreColumn::with_rule_set(Variant::Balut.rule_set());
+        for score in [16, 16, 12] {
+            st.confirm_score(Boxes::Fours, score).unwrap();
+        }
+        assert!(!st.has_score_in(Boxes::Fours));
+        assert_eq!(st.get_award(Boxes::Fours), 0);
+        st.confirm_score(Boxes::Fours, 8).unwrap();
+        assert!(st.has_score_in(Boxes::Fours));
+        assert_eq!(st.get_score(Boxes::Fours), Some(52));
+        assert_eq!(st.get_award(Boxes::Fours), 2);
+        assert!(st.confirm_score(Boxes::Fours, 4).is_err());
+
+        for score in [15, 20, 0, 15] {
+            st.confirm_score(Boxes::LargeStraight, score).unwrap();
+        }
+        assert_eq!(st.get_award(Boxes::LargeStraight), 0);
+        for score in [50, 0, 30] {
+            st.confirm_score(Boxes::Yahtzee, score).unwrap();
+        }
+        assert_eq!(st.get_award(Boxes::Yahtzee), 4);
+        assert_eq!(st.get_num_filled_scores(), 11);
+        assert_eq!(st.get_total_score(), 52 + 50 + 80);
+        assert_eq!(st.get_points(), 2 + 4 - 2);
+
+        assert_eq!(Balut.points_for_total(349), -1);
+        assert_eq!(Balut.points_for_total(350), 0);
+        assert_eq!(Balut.points_for_total(612), 5);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::dice::{Dice, Pip};
+use crate::rule_set::{RuleSet, Variant};
+use crate::scoring::Boxes;
+use enum_iterator::Sequence;
+use std::sync::OnceLock;
+
+/* Scores of every box for every full hand of a rule set, indexed by the dice */
+pub struct ScoreLookup {
+    dice_num: usize,
+    scores: Vec<Vec<u32>>,
+}
+
+impl ScoreLookup {
+    /* Covers the pips of every die in DieSpec::ALL */
+    pub fn new(rule: &dyn RuleSet) -> Self {
+        let dice_num = rule.dice_num();
+        let pips: Vec<_> = Pip::all().collect();
+        let mut scores = vec![vec![]; Self::num_hands(dice_num)];
+        for dice in Dice::multisets(dice_num, &pips) {
+            scores[Self::index(&dice)] = enum_iterator::all::<Boxes>()
+                .map(|b| {
+                    if rule.boxes().contains(&b) {
+                        rule.score(b, &dice)
+                    } else {
+                        0
+                    }
+                })
+                .collect();
+        }
+        Self { dice_num, scores }
+    }
+
+    /* The table of the variant, built on the first use and shared afterwards */
+    pub fn of(variant: Variant) -> &'static Self {
+        static LOOKUPS: [OnceLock<ScoreLookup>; Variant::CARDINALITY] =
+            [const { OnceLock::new() }; Variant::CARDINALITY];
+        LOOKUPS[variant as usize].get_or_init(|| Self::new(&*variant.rule_set()))
+    }
+
+    /* None if the dice are not a full hand of the rule set */
+    pub fn get(&self, b: Boxes, dice: &Dice) -> Option<u32> {
+        if dice.len() != self.dice_num {
+            return None;
+        }
+        Some(self.scores[Self::index(dice)][b as usize])
+    }
+
+    fn num_hands(dice_num: usize) -> usize {
+        binomial(dice_num + Pip::all().count() - 1, dice_num)
+    }
+
+    /*
+     * Lays the dice out as stars and bars, one bar after the dice of each pip but the highest,
+     * and ranks the positions of the bars as a combination, so that the hands of the same number
+     * of dice are numbered from 0 without gaps.
+     */
+    fn index(dice: &Dice) -> usize {
+        let mut stars = 0;
+        Pip::all()
+            .take((Pip::MAX - Pip::MIN) as usize)
+            .enumerate()
+            .map(|(bar, pip)| {
+                stars += dice.count(pip);
+                binomial(stars + bar, bar + 1)
+            })
+            .sum()
+    }
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::score_lookup::*;
+
+    fn scores<const N: usize>(variant: Variant, dice: [u32; N]) -> Vec<(Boxes, u32)> {
+        let dice = Dice::try_from(dice).unwrap();
+        let lookup = ScoreLookup::of(variant);
+        variant
+            .rule_set()
+            .boxes()
+            .iter()
+            .map(|&b| (b, lookup.get(b, &dice).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn index_test() {
+        for num in 0..=Dice::MAX_LEN {
+            let pips: Vec<_> = Pip::all().collect();
+            let mut indices: Vec<_> = Dice::multisets(num, &pips)
+                .iter()
+                .map(ScoreLookup::index)
+                .collect();
+            indices.sort();
+            assert_eq!(
+                indices,
+                (0..ScoreLookup::num_hands(num)).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn yahtzee_lookup_test() {
+        for variant in [Variant::Yahtzee, Variant::TripleYahtzee] {
+            assert!(
+                scores(variant, [3, 3, 3, 5, 5])
+                    == vec![
+                        (Boxes::Aces, 0),
+                        (Boxes::Twos, 0),
+                        (Boxes::Threes, 9),
+                        (Boxes::Fours, 0),
+                        (Boxes::Fives, 10),
+                        (Boxes::Sixes, 0),
+                        (Boxes::ThreeOfaAKind, 19),
+                        (Boxes::FourOfaAKind, 0),
+                        (Boxes::FullHouse, 25),
+                        (Boxes::SmallStraight, 0),
+                        (Boxes::LargeStraight, 0),
+                        (Boxes::Yahtzee, 0),
+                        (Boxes::Chance, 19),
+                    ]
+            );
+            assert!(
+                scores(variant, [2, 3, 4, 5, 6])
+                    == vec![
+                        (Boxes::Aces, 0),
+                        (Boxes::Twos, 2),
+                        (Boxes::Threes, 3),
+                        (Boxes::Fours, 4),
+                        (Boxes::Fives, 5),
+                        (Boxes::Sixes, 6),
+                        (Boxes::ThreeOfaAKind, 0),
+                        (Boxes::FourOfaAKind, 0),
+                        (Boxes::FullHouse, 0),
+                        (Boxes::SmallStraight, 30),
+                        (Boxes::LargeStraight, 40),
+                        (Boxes::Yahtzee, 0),
+                        (Boxes::Chance, 20),
+                    ]
+            );
+        }
+    }
+
+    #[test]
+    fn variants_lookup_test() {
+        assert!(
+            scores(Variant::Yatzy, [4, 4, 4, 2, 2])
+                == vec![
+                    (Boxes::Aces, 0),
+                    (Boxes::Twos, 4),
+                    (Boxes::Threes, 0),
+                    (Boxes::Fours, 12),
+                    (Boxes::Fives, 0),
+                    (Boxes::Sixes, 0),
+                    (Boxes::OnePair, 8),
+                    (Boxes::TwoPairs, 12),
+                    (Boxes::ThreeOfaAKind, 12),
+                    (Boxes::FourOfaAKind, 0),
+                    (Boxes::SmallStraight, 0),
+                    (Boxes::LargeStraight, 0),
+                    (Boxes::FullHouse, 16),
+                    (Boxes::Chance, 16),
+                    (Boxes::Yahtzee, 0),
+                ]
+        );
+        assert!(
+            scores(Variant::MaxiYatzy, [5, 5, 3, 3, 1, 1])
+                == vec![
+                    (Boxes::Aces, 2),
+                    (Boxes::Twos, 0),
+                    (Boxes::Threes, 6),
+                    (Boxes::Fours, 0),
+                    (Boxes::Fives, 10),
+                    (Boxes::Sixes, 0),
+                    (Boxes::OnePair, 10),
+                    (Boxes::TwoPairs, 16),
+                    (Boxes::ThreePairs, 18),
+                    (Boxes::ThreeOfaAKind, 0),
+                    (Boxes::FourOfaAKind, 0),
+                    (Boxes::FiveOfaAKind, 0),
+                    (Boxes::SmallStraight, 0),
+                    (Boxes::LargeStraight, 0),
+                    (Boxes::FullStraight, 0),
+                    (Boxes::FullHouse, 0),
+                    (Boxes::Castle, 0),
+                    (Boxes::Tower, 0),
+                    (Boxes::Chance, 18),
+                    (Boxes::Yahtzee, 0),
+                ]
+        );
+        assert!(
+            scores(Variant::Yamb, [1, 2, 3, 4, 5])
+                == vec![
+                    (Boxes::Aces, 1),
+                    (Boxes::Twos, 2),
+                    (Boxes::Threes, 3),
+                    (Boxes::Fours, 4),
+                    (Boxes::Fives, 5),
+                    (Boxes::Sixes, 0),
+                    (Boxes::Max, 15),
+                    (Boxes::Min, 15),
+                    (Boxes::ThreeOfaAKind, 0),
+                    (Boxes::LargeStraight, 35),
+                    (Boxes::FullHouse, 0),
+                    (Boxes::FourOfaAKind, 0),
+                    (Boxes::Yahtzee, 0),
+                ]
+        );
+        assert!(
+            scores(Variant::Generala, [6, 6, 6, 6, 1])
+                == vec![
+                    (Boxes::Aces, 1),
+                    (Boxes::Twos, 0),
+                    (Boxes::Threes, 0),
+                    (Boxes::Fours, 0),
+                    (Boxes::Fives, 0),
+                    (Boxes::Sixes, 24),
+                    (Boxes::LargeStraight, 0),
+                    (Boxes::FullHouse, 0),
+                    (Boxes::FourOfaAKind, 40),
+                    (Boxes::Yahtzee, 0),
+                    (Boxes::DoubleYahtzee, 0),
+                ]
+        );
+        assert!(
+            scores(Variant::Yacht, [2, 2, 5, 5, 5])
+                == vec![
+                    (Boxes::Aces, 0),
+                    (Boxes::Twos, 4),
+                    (Boxes::Threes, 0),
+                    (Boxes::Fours, 0),
+                    (Boxes::Fives, 15),
+                    (Boxes::Sixes, 0),
+                    (Boxes::FullHouse, 19),
+                    (Boxes::FourOfaAKind, 0),
+                    (Boxes::SmallStraight, 0),
+                    (Boxes::LargeStraight, 0),
+                    (Boxes::Chance, 19),
+                    (Boxes::Yahtzee, 0),
+                ]
+        );
+        assert!(
+            scores(Variant::Balut, [3, 4, 5, 6, 6])
+                == vec![
+                    (Boxes::Fours, 4),
+                    (Boxes::Fives, 5),
+                    (Boxes::Sixes, 12),
+                    (Boxes::LargeStraight, 0),
+                    (Boxes::FullHouse, 0),
+                    (Boxes::Chance, 24),
+                    (Boxes::Yahtzee, 0),
+                ]
+        );
+    }
+
+    #[test]
+    fn partial_hand_test() {
+        let rule = Variant::Yahtzee.rule_set();
+        let dice = Dice::try_from([3, 3, 3]).unwrap();
+        assert_eq!(rule.lookup().get(Boxes::Threes, &dice), None);
+        assert_eq!(rule.look_up_score(Boxes::Threes, &dice), 9);
+        assert_eq!(rule.look_up_score(Boxes::Chance, &Dice::default()), 0);
+    }
+}
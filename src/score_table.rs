@@ -1,7 +1,10 @@
-use crate::hand::{Die, Hand};
+use crate::dice::Dice;
+use crate::play::TurnContext;
+use crate::rule_set::{AwardCondition, ClassicYahtzee, FillOrder, RuleSet};
 use crate::scoring::Boxes;
 use anyhow::{bail, Result};
 use std::collections::HashMap;
+use std::rc::Rc;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -10,82 +13,105 @@ pub enum RecordError {
     TryToFillFilledRecord,
 }
 
+#[derive(Debug, Error)]
+pub enum ScoreTableError {
+    #[error("The box cannot be filled with the current hand")]
+    UnselectableBox,
+    #[error("The box cannot be announced")]
+    UnannounceableBox,
+}
+
 #[derive(Clone)]
 struct Record {
-    score: Option<u32>,
+    entries: Vec<Option<u32>>,
 }
 
 impl Record {
-    fn new() -> Self {
-        Record { score: None }
-    }
-
-    fn new_with_score(score: u32) -> Self {
-        Record { score: Some(score) }
+    fn new(num_entries: usize) -> Self {
+        Record {
+            entries: vec![None; num_entries],
+        }
     }
 
     fn fill(&mut self, score: u32) -> Result<()> {
-        if self.is_filled() {
+        let Some(entry) = self.entries.iter_mut().find(|e| e.is_none()) else {
             bail!(RecordError::TryToFillFilledRecord);
-        }
+        };
 
-        self.score = Some(score);
+        *entry = Some(score);
 
         Ok(())
     }
 
-    fn get_score(&self) -> &Option<u32> {
-        &self.score
+    /* The sum of the filled entries */
+    fn get_score(&self) -> Option<u32> {
+        self.entries
+            .iter()
+            .flatten()
+            .fold(None, |sum, &e| Some(sum.unwrap_or(0) + e))
+    }
+
+    fn get_entries(&self) -> &[Option<u32>] {
+        &self.entries
+    }
+
+    fn get_num_filled_entries(&self) -> usize {
+        self.entries.iter().flatten().count()
     }
 
     fn is_filled(&self) -> bool {
-        matches!(self.score, Some(..))
+        self.entries.iter().all(|e| e.is_some())
     }
 }
 
-pub struct ScoreTable {
+#[derive(Clone)]
+pub struct ScoreColumn {
+    rule: Rc<dyn RuleSet>,
+    order: FillOrder,
     table: HashMap<Boxes, Record>,
+    yahtzee_bonus_count: u32,
 }
 
-impl Default for ScoreTable {
+impl Default for ScoreColumn {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl ScoreTable {
-    const BONUS_TARGETS: [(Boxes, u32); 6] = {
-        [
-            (Boxes::Aces, 1),
-            (Boxes::Twos, 2),
-            (Boxes::Threes, 3),
-            (Boxes::Fours, 4),
-            (Boxes::Fives, 5),
-            (Boxes::Sixes, 6),
-        ]
-    };
-    pub const BONUS_THRESHOLD: u32 = {
-        /* This means (1 + 2 + 3 + 4 + 5 + 6) * 3. */
-        let mut sum = 0;
-        let mut i = 1;
-        while i <= Die::PIPS.len() {
-            sum += i;
-            i += 1;
-        }
-        (sum * 3) as u32
-    };
-    const BONUS_POINT: u32 = 35;
-
+impl ScoreColumn {
     pub fn new() -> Self {
-        ScoreTable {
-            table: HashMap::from_iter(enum_iterator::all::<Boxes>().map(|b| (b, Record::new()))),
+        Self::with_rule_set(Rc::new(ClassicYahtzee))
+    }
+
+    pub fn with_rule_set(rule: Rc<dyn RuleSet>) -> Self {
+        Self::with_fill_order(rule, FillOrder::Free)
+    }
+
+    pub fn with_fill_order(rule: Rc<dyn RuleSet>, order: FillOrder) -> Self {
+        ScoreColumn {
+            table: HashMap::from_iter(
+                rule.boxes()
+                    .iter()
+                    .map(|&b| (b, Record::new(rule.entries_per_box()))),
+            ),
+            rule,
+            order,
+            yahtzee_bonus_count: 0,
         }
     }
 
-    pub fn get_score(&self, b: Boxes) -> &Option<u32> {
+    pub fn get_fill_order(&self) -> FillOrder {
+        self.order
+    }
+
+    pub fn get_score(&self, b: Boxes) -> Option<u32> {
         self.table[&b].get_score()
     }
 
+    pub fn get_entries(&self, b: Boxes) -> &[Option<u32>] {
+        self.table[&b].get_entries()
+    }
+
     pub fn has_score_in(&self, b: Boxes) -> bool {
         self.table[&b].is_filled()
     }
@@ -96,52 +122,90 @@ impl ScoreTable {
 
     pub fn get_num_filled_scores(&self) -> usize {
         self.table
-            .iter()
-            .filter(|(.., row)| row.is_filled())
-            .count()
+            .values()
+            .map(|row| row.get_num_filled_entries())
+            .sum()
     }
 
     pub fn confirm_score(&mut self, b: Boxes, score: u32) -> Result<()> {
         self.table.get_mut(&b).unwrap().fill(score)
     }
 
-    pub fn get_total_upper_score(&self) -> u32 {
-        ScoreTable::BONUS_TARGETS
-            .iter()
-            .map(|&(b, ..)| self.get_score(b).unwrap_or(0))
-            .sum()
+    pub fn is_joker(&self, dice: &Dice) -> bool {
+        self.rule.is_joker(self, dice)
     }
 
-    pub fn get_total_upper_score_if_filled_by(&self, b: Boxes, score: u32) -> u32 {
-        let mut dummy_table = HashMap::new();
-        dummy_table.clone_from(&self.table);
+    pub fn can_fill(&self, b: Boxes, dice: &Dice) -> bool {
+        self.is_in_fill_order(b) && self.rule.can_fill(self, b, dice)
+    }
+
+    fn is_in_fill_order(&self, b: Boxes) -> bool {
+        let mut unfilled = self.rule.boxes().iter().filter(|&&b| !self.has_score_in(b));
+        match self.order {
+            FillOrder::Down => unfilled.next() == Some(&b),
+            FillOrder::Up => unfilled.next_back() == Some(&b),
+            FillOrder::Free | FillOrder::Announce => true,
+        }
+    }
 
-        let mut dummy_score_table = ScoreTable { table: dummy_table };
-        if !dummy_score_table.has_score_in(b) {
-            dummy_score_table
-                .table
-                .insert(b, Record::new_with_score(score));
+    pub fn score_of(&self, b: Boxes, dice: &Dice, turn: &TurnContext) -> u32 {
+        self.rule.score_in(self, b, dice, turn)
+    }
+
+    pub fn confirm_dice(&mut self, b: Boxes, dice: &Dice, turn: &TurnContext) -> Result<()> {
+        if !self.can_fill(b, dice) {
+            bail!(ScoreTableError::UnselectableBox);
         }
 
-        dummy_score_table.get_total_upper_score()
+        let score = self.score_of(b, dice, turn);
+        if self.is_joker(dice) {
+            self.yahtzee_bonus_count += 1;
+        }
+        self.confirm_score(b, score)
+    }
+
+    pub fn get_yahtzee_bonus_count(&self) -> u32 {
+        self.yahtzee_bonus_count
+    }
+
+    pub fn get_yahtzee_bonus(&self) -> u32 {
+        self.yahtzee_bonus_count * self.rule.yahtzee_bonus_point().unwrap_or(0)
+    }
+
+    pub fn get_total_upper_score(&self) -> u32 {
+        self.rule.upper_bonus().map_or(0, |ub| {
+            ub.targets
+                .iter()
+                .map(|&(b, ..)| self.get_score(b).unwrap_or(0))
+                .sum()
+        })
+    }
+
+    pub fn get_total_upper_score_if_filled_by(&self, b: Boxes, score: u32) -> u32 {
+        self.dummy_filled_by(b, score).get_total_upper_score()
     }
 
     pub fn calculate_bonus(&self) -> Option<u32> {
+        let Some(ub) = self.rule.upper_bonus() else {
+            return Some(0);
+        };
+
         let current = self.get_total_upper_score();
-        let max: u32 = ScoreTable::BONUS_TARGETS
+        let max: u32 = ub
+            .targets
             .iter()
             .map(|&(b, p)| {
                 if self.has_score_in(b) {
                     self.get_score(b).unwrap()
                 } else {
-                    p * (Hand::DICE_NUM as u32)
+                    p * (self.rule.dice_num() as u32)
                 }
             })
             .sum();
 
-        if current >= ScoreTable::BONUS_THRESHOLD {
-            Some(ScoreTable::BONUS_POINT)
-        } else if max >= ScoreTable::BONUS_THRESHOLD {
+        if current >= ub.threshold {
+            Some(ub.point)
+        } else if max >= ub.threshold {
             None
         } else {
             Some(0)
@@ -149,62 +213,265 @@ impl ScoreTable {
     }
 
     pub fn calculate_bonus_if_filled_by(&self, b: Boxes, score: u32) -> Option<u32> {
-        let mut dummy_table = HashMap::new();
-        dummy_table.clone_from(&self.table);
+        self.dummy_filled_by(b, score).calculate_bonus()
+    }
 
-        let mut dummy_score_table = ScoreTable { table: dummy_table };
-        if !dummy_score_table.has_score_in(b) {
-            dummy_score_table
-                .table
-                .insert(b, Record::new_with_score(score));
+    pub fn get_total_score(&self) -> u32 {
+        self.rule.boxes_total(self) + self.calculate_bonus().unwrap_or(0) + self.get_yahtzee_bonus()
+    }
+
+    pub fn get_total_score_if_filled_by(&self, b: Boxes, score: u32) -> u32 {
+        self.dummy_filled_by(b, score).get_total_score()
+    }
+
+    pub fn get_total_score_if_filled_with(&self, b: Boxes, dice: &Dice, turn: &TurnContext) -> u32 {
+        if !self.can_fill(b, dice) {
+            return self.get_total_score();
         }
 
-        dummy_score_table.calculate_bonus()
+        let chip = if self.is_joker(dice) {
+            self.rule.yahtzee_bonus_point().unwrap_or(0)
+        } else {
+            0
+        };
+        self.get_total_score_if_filled_by(b, self.score_of(b, dice, turn)) + chip
     }
 
-    pub fn get_total_score(&self) -> u32 {
-        let sum: u32 = self
-            .table
-            .keys()
-            .map(|&b| self.get_score(b).unwrap_or(0))
-            .sum();
+    /* Points awarded for the total of the category */
+    pub fn get_award(&self, b: Boxes) -> u32 {
+        let Some(award) = self.rule.category_awards().iter().find(|a| a.target == b) else {
+            return 0;
+        };
 
-        sum + self.calculate_bonus().unwrap_or(0)
+        let scored = self
+            .get_entries(b)
+            .iter()
+            .filter(|e| matches!(e, Some(s) if *s > 0))
+            .count();
+        match award.condition {
+            AwardCondition::AtLeast(threshold) if self.get_score(b).unwrap_or(0) >= threshold => {
+                award.point
+            }
+            AwardCondition::AllScored if scored == self.get_entries(b).len() => award.point,
+            AwardCondition::EachScored => award.point * scored as u32,
+            _ => 0,
+        }
     }
 
-    pub fn get_total_score_if_filled_by(&self, b: Boxes, score: u32) -> u32 {
-        let mut dummy_table = HashMap::new();
-        dummy_table.clone_from(&self.table);
+    pub fn get_points(&self) -> i32 {
+        let awards: u32 = self.rule.boxes().iter().map(|&b| self.get_award(b)).sum();
+        awards as i32 + self.rule.points_for_total(self.get_total_score())
+    }
 
-        let mut dummy_score_table = ScoreTable { table: dummy_table };
-        if !dummy_score_table.has_score_in(b) {
-            dummy_score_table
-                .table
-                .insert(b, Record::new_with_score(score));
+    fn dummy_filled_by(&self, b: Boxes, score: u32) -> ScoreColumn {
+        let mut dummy_column = self.clone();
+        if !dummy_column.has_score_in(b) {
+            dummy_column.confirm_score(b, score).unwrap();
         }
 
-        dummy_score_table.get_total_score()
+        dummy_column
+    }
+}
+
+#[derive(Clone)]
+pub struct ScoreTable {
+    rule: Rc<dyn RuleSet>,
+    columns: Vec<ScoreColumn>,
+    announced: Option<Boxes>,
+    won_outright: bool,
+}
+
+impl Default for ScoreTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScoreTable {
+    pub fn new() -> Self {
+        Self::with_rule_set(Rc::new(ClassicYahtzee))
+    }
+
+    pub fn with_rule_set(rule: Rc<dyn RuleSet>) -> Self {
+        ScoreTable {
+            columns: rule
+                .columns()
+                .iter()
+                .map(|spec| ScoreColumn::with_fill_order(rule.clone(), spec.order))
+                .collect(),
+            rule,
+            announced: None,
+            won_outright: false,
+        }
+    }
+
+    pub fn get_column(&self, col: usize) -> &ScoreColumn {
+        &self.columns[col]
+    }
+
+    pub fn get_num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn get_multiplier(&self, col: usize) -> u32 {
+        self.rule.columns()[col].multiplier
+    }
+
+    pub fn has_all_scores(&self) -> bool {
+        self.columns.iter().all(|c| c.has_all_scores())
+    }
+
+    pub fn get_num_filled_scores(&self) -> usize {
+        self.columns.iter().map(|c| c.get_num_filled_scores()).sum()
+    }
+
+    /* The announce column can be filled only with the box announced in the current turn. */
+    pub fn can_fill(&self, col: usize, b: Boxes, dice: &Dice) -> bool {
+        let column = &self.columns[col];
+        let is_announce_column = column.get_fill_order() == FillOrder::Announce;
+        match self.announced {
+            Some(announced) => is_announce_column && b == announced && column.can_fill(b, dice),
+            None => !is_announce_column && column.can_fill(b, dice),
+        }
+    }
+
+    pub fn confirm_dice(
+        &mut self,
+        col: usize,
+        b: Boxes,
+        dice: &Dice,
+        turn: &TurnContext,
+    ) -> Result<()> {
+        if !self.can_fill(col, b, dice) {
+            bail!(ScoreTableError::UnselectableBox);
+        }
+
+        self.columns[col].confirm_dice(b, dice, turn)?;
+        self.announced = None;
+        if self.rule.wins_outright(dice, turn) {
+            self.won_outright = true;
+        }
+
+        Ok(())
+    }
+
+    /* A classic table where only the boxes are left, so that the solver finishes at once */
+    #[cfg(test)]
+    pub fn with_only_left(left: &[Boxes]) -> Self {
+        let mut table = Self::new();
+        let filler = Dice::try_from([6, 6, 6, 6, 2]).unwrap();
+        let turn = TurnContext::new(crate::play::Play::DEFAULT_ROLLS_PER_TURN);
+        for &b in ClassicYahtzee.boxes() {
+            if !left.contains(&b) {
+                table.confirm_dice(0, b, &filler, &turn).unwrap();
+            }
+        }
+        table
+    }
+
+    pub fn has_announce_column(&self) -> bool {
+        self.columns
+            .iter()
+            .any(|c| c.get_fill_order() == FillOrder::Announce)
+    }
+
+    pub fn can_announce(&self, col: usize, b: Boxes) -> bool {
+        let column = &self.columns[col];
+        self.announced.is_none()
+            && column.get_fill_order() == FillOrder::Announce
+            && !column.has_score_in(b)
+    }
+
+    /* An announcement is required once only the announce column has empty boxes. */
+    pub fn must_announce(&self) -> bool {
+        self.has_announce_column()
+            && self
+                .columns
+                .iter()
+                .filter(|c| c.get_fill_order() != FillOrder::Announce)
+                .all(|c| c.has_all_scores())
+    }
+
+    pub fn announce(&mut self, col: usize, b: Boxes) -> Result<()> {
+        if !self.can_announce(col, b) {
+            bail!(ScoreTableError::UnannounceableBox);
+        }
+
+        self.announced = Some(b);
+
+        Ok(())
+    }
+
+    pub fn get_announced_box(&self) -> Option<Boxes> {
+        self.announced
+    }
+
+    pub fn has_won_outright(&self) -> bool {
+        self.won_outright
+    }
+
+    /* None if the rules rank players by the score instead of points */
+    pub fn get_points(&self) -> Option<i32> {
+        if self.rule.category_awards().is_empty() {
+            return None;
+        }
+
+        Some(self.columns.iter().map(|c| c.get_points()).sum())
+    }
+
+    pub fn get_column_total_score(&self, col: usize) -> u32 {
+        self.columns[col].get_total_score() * self.get_multiplier(col)
+    }
+
+    pub fn get_column_total_score_if_filled_with(
+        &self,
+        col: usize,
+        b: Boxes,
+        dice: &Dice,
+        turn: &TurnContext,
+    ) -> u32 {
+        self.columns[col].get_total_score_if_filled_with(b, dice, turn) * self.get_multiplier(col)
+    }
+
+    pub fn get_total_score(&self) -> u32 {
+        (0..self.columns.len())
+            .map(|col| self.get_column_total_score(col))
+            .sum()
+    }
+
+    pub fn get_total_score_if_filled_with(
+        &self,
+        col: usize,
+        b: Boxes,
+        dice: &Dice,
+        turn: &TurnContext,
+    ) -> u32 {
+        self.get_total_score() - self.get_column_total_score(col)
+            + self.get_column_total_score_if_filled_with(col, b, dice, turn)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::hand::Die;
+    use crate::play::Play;
+    use crate::rule_set::Variant;
     use crate::score_table::*;
 
     #[test]
     fn test_record() {
-        let mut record = Record::new();
+        let mut record = Record::new(1);
         assert!(!record.is_filled());
 
         let score: u32 = 32;
         record.fill(score).unwrap();
-        assert_eq!(record.get_score(), &Some(score));
+        assert_eq!(record.get_score(), Some(score));
         assert!(record.is_filled());
     }
 
     #[test]
     fn test_score_table() {
-        let mut score_table = ScoreTable::new();
+        let mut score_table = ScoreColumn::new();
         let b = Boxes::Chance;
         let score: u32 = 21;
 
@@ -212,23 +479,23 @@ mod tests {
 
         score_table.confirm_score(b, score).unwrap();
         assert!(score_table.has_score_in(b));
-        assert_eq!(score_table.get_score(b), &Some(score));
+        assert_eq!(score_table.get_score(b), Some(score));
     }
 
     #[test]
     fn test_get_total_upper_score() {
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
         assert_eq!(
             score_table.get_total_upper_score(),
-            ScoreTable::BONUS_THRESHOLD
+            ClassicYahtzee::BONUS_THRESHOLD
         );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 2).unwrap();
         }
 
@@ -237,8 +504,8 @@ mod tests {
             Die::PIPS.iter().sum::<u32>() * 2
         );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS[1..].iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS[1..].iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
@@ -247,8 +514,8 @@ mod tests {
             Die::PIPS[1..].iter().sum::<u32>() * 3
         );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS[1..].iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS[1..].iter() {
             score_table.confirm_score(b, p * 2).unwrap();
         }
 
@@ -260,18 +527,18 @@ mod tests {
 
     #[test]
     fn test_get_total_upper_score_if_filled_by() {
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
         assert_eq!(
             score_table.get_total_upper_score_if_filled_by(Boxes::Chance, 20),
-            ScoreTable::BONUS_THRESHOLD
+            ClassicYahtzee::BONUS_THRESHOLD
         );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 2).unwrap();
         }
 
@@ -280,12 +547,12 @@ mod tests {
             Die::PIPS.iter().sum::<u32>() * 2
         );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS[1..].iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS[1..].iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
-        let (b, p) = ScoreTable::BONUS_TARGETS[0];
+        let (b, p) = ClassicYahtzee::BONUS_TARGETS[0];
         assert_eq!(
             score_table.get_total_upper_score_if_filled_by(b, p * 3),
             Die::PIPS.iter().sum::<u32>() * 3
@@ -298,29 +565,32 @@ mod tests {
 
     #[test]
     fn test_calculate_bonus() {
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
-        assert_eq!(score_table.calculate_bonus(), Some(ScoreTable::BONUS_POINT));
+        assert_eq!(
+            score_table.calculate_bonus(),
+            Some(ClassicYahtzee::BONUS_POINT)
+        );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 2).unwrap();
         }
 
         assert_eq!(score_table.calculate_bonus(), Some(0));
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS[1..].iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS[1..].iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
         assert_eq!(score_table.calculate_bonus(), None);
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS[1..].iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS[1..].iter() {
             score_table.confirm_score(b, p * 2).unwrap();
         }
 
@@ -329,48 +599,48 @@ mod tests {
 
     #[test]
     fn test_calculate_bonus_if_filled_by() {
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
         let result = score_table.calculate_bonus_if_filled_by(Boxes::Chance, 20);
-        assert_eq!(result, Some(ScoreTable::BONUS_POINT));
+        assert_eq!(result, Some(ClassicYahtzee::BONUS_POINT));
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 2).unwrap();
         }
 
         let result = score_table.calculate_bonus_if_filled_by(Boxes::Chance, 20);
         assert_eq!(result, Some(0));
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS[1..].iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS[1..].iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
-        let (b, p) = ScoreTable::BONUS_TARGETS[0];
+        let (b, p) = ClassicYahtzee::BONUS_TARGETS[0];
         let result = score_table.calculate_bonus_if_filled_by(b, p * 2);
         assert_eq!(result, Some(0));
         let result = score_table.calculate_bonus_if_filled_by(b, p * 3);
-        assert_eq!(result, Some(ScoreTable::BONUS_POINT));
+        assert_eq!(result, Some(ClassicYahtzee::BONUS_POINT));
     }
 
     #[test]
     fn test_get_total_score() {
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
         assert_eq!(
             score_table.get_total_score(),
-            ScoreTable::BONUS_POINT + ScoreTable::BONUS_THRESHOLD
+            ClassicYahtzee::BONUS_POINT + ClassicYahtzee::BONUS_THRESHOLD
         );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 2).unwrap();
         }
 
@@ -379,8 +649,8 @@ mod tests {
             Die::PIPS.iter().sum::<u32>() * 2
         );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS[1..].iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS[1..].iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
@@ -389,8 +659,8 @@ mod tests {
             Die::PIPS[1..].iter().sum::<u32>() * 3
         );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS[1..].iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS[1..].iter() {
             score_table.confirm_score(b, p * 2).unwrap();
         }
 
@@ -402,18 +672,18 @@ mod tests {
 
     #[test]
     fn test_get_total_score_if_filled_by() {
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
         assert_eq!(
             score_table.get_total_score_if_filled_by(Boxes::Chance, 20),
-            ScoreTable::BONUS_POINT + ScoreTable::BONUS_THRESHOLD + 20
+            ClassicYahtzee::BONUS_POINT + ClassicYahtzee::BONUS_THRESHOLD + 20
         );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS.iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS.iter() {
             score_table.confirm_score(b, p * 2).unwrap();
         }
 
@@ -422,19 +692,155 @@ mod tests {
             Die::PIPS.iter().sum::<u32>() * 2 + 20
         );
 
-        let mut score_table = ScoreTable::new();
-        for &(b, p) in ScoreTable::BONUS_TARGETS[1..].iter() {
+        let mut score_table = ScoreColumn::new();
+        for &(b, p) in ClassicYahtzee::BONUS_TARGETS[1..].iter() {
             score_table.confirm_score(b, p * 3).unwrap();
         }
 
-        let (b, p) = ScoreTable::BONUS_TARGETS[0];
+        let (b, p) = ClassicYahtzee::BONUS_TARGETS[0];
         assert_eq!(
             score_table.get_total_score_if_filled_by(b, p * 3),
-            Die::PIPS.iter().sum::<u32>() * 3 + ScoreTable::BONUS_POINT
+            Die::PIPS.iter().sum::<u32>() * 3 + ClassicYahtzee::BONUS_POINT
         );
         assert_eq!(
             score_table.get_total_score_if_filled_by(b, p * 2),
             Die::PIPS[1..].iter().sum::<u32>() * 3 + 2
         );
     }
+
+    #[test]
+    fn test_yahtzee_bonus_and_joker() {
+        let yahtzee = Dice::try_from([4, 4, 4, 4, 4]).unwrap();
+        let turn = TurnContext::new(Play::DEFAULT_ROLLS_PER_TURN);
+
+        let mut score_table = ScoreColumn::new();
+        assert!(!score_table.is_joker(&yahtzee));
+        score_table
+            .confirm_dice(Boxes::Yahtzee, &yahtzee, &turn)
+            .unwrap();
+        assert_eq!(score_table.get_yahtzee_bonus_count(), 0);
+
+        /* The matching upper box is forced while it is open. */
+        assert!(score_table.is_joker(&yahtzee));
+        assert!(score_table.can_fill(Boxes::Fours, &yahtzee));
+        assert!(!score_table.can_fill(Boxes::FullHouse, &yahtzee));
+        assert!(!score_table.can_fill(Boxes::Aces, &yahtzee));
+        score_table
+            .confirm_dice(Boxes::Fours, &yahtzee, &turn)
+            .unwrap();
+        assert_eq!(score_table.get_yahtzee_bonus_count(), 1);
+        assert_eq!(score_table.get_score(Boxes::Fours), Some(20));
+
+        /* Then any lower box at full value. */
+        assert!(!score_table.can_fill(Boxes::Aces, &yahtzee));
+        assert!(score_table.can_fill(Boxes::LargeStraight, &yahtzee));
+        assert_eq!(
+            score_table.score_of(Boxes::LargeStraight, &yahtzee, &turn),
+            40
+        );
+        assert_eq!(score_table.score_of(Boxes::FullHouse, &yahtzee, &turn), 25);
+        assert_eq!(
+            score_table.get_total_score_if_filled_with(Boxes::FullHouse, &yahtzee, &turn),
+            50 + 20 + 25 + ClassicYahtzee::YAHTZEE_BONUS_POINT * 2
+        );
+        score_table
+            .confirm_dice(Boxes::FullHouse, &yahtzee, &turn)
+            .unwrap();
+        assert_eq!(
+            score_table.get_total_score(),
+            50 + 20 + 25 + ClassicYahtzee::YAHTZEE_BONUS_POINT * 2
+        );
+
+        /* Then any upper box once the lower section is full. */
+        for b in [
+            Boxes::ThreeOfaAKind,
+            Boxes::FourOfaAKind,
+            Boxes::SmallStraight,
+            Boxes::LargeStraight,
+            Boxes::Chance,
+        ] {
+            score_table.confirm_score(b, 0).unwrap();
+        }
+        assert!(score_table.can_fill(Boxes::Aces, &yahtzee));
+        assert_eq!(score_table.score_of(Boxes::Aces, &yahtzee, &turn), 0);
+        match score_table.confirm_dice(Boxes::Fours, &yahtzee, &turn) {
+            Ok(..) => panic!("Must not return Ok"),
+            Err(e) => match e.downcast_ref::<ScoreTableError>() {
+                Some(ScoreTableError::UnselectableBox) => (),
+                _ => panic!("Should return ScoreTableError::UnselectableBox"),
+            },
+        }
+
+        /* A scratched Yahtzee box gives neither a bonus nor a Joker. */
+        let mut score_table = ScoreColumn::new();
+        score_table.confirm_score(Boxes::Yahtzee, 0).unwrap();
+        assert!(!score_table.is_joker(&yahtzee));
+        assert!(score_table.can_fill(Boxes::Aces, &yahtzee));
+        score_table
+            .confirm_dice(Boxes::Aces, &yahtzee, &turn)
+            .unwrap();
+        assert_eq!(score_table.get_yahtzee_bonus_count(), 0);
+    }
+
+    #[test]
+    fn test_multiple_columns() {
+        let mut score_table = ScoreTable::with_rule_set(Variant::TripleYahtzee.rule_set());
+        assert_eq!(score_table.get_num_columns(), 3);
+
+        let dice = Dice::try_from([2, 2, 2, 5, 5]).unwrap();
+        let turn = TurnContext::new(Play::DEFAULT_ROLLS_PER_TURN);
+        score_table
+            .confirm_dice(0, Boxes::Chance, &dice, &turn)
+            .unwrap();
+        score_table
+            .confirm_dice(2, Boxes::FullHouse, &dice, &turn)
+            .unwrap();
+        assert!(score_table.can_fill(1, Boxes::Chance, &dice));
+        assert!(!score_table.can_fill(2, Boxes::FullHouse, &dice));
+        assert_eq!(score_table.get_num_filled_scores(), 2);
+        assert_eq!(score_table.get_column_total_score(2), 75);
+        assert_eq!(score_table.get_total_score(), 16 + 75);
+        assert_eq!(
+            score_table.get_total_score_if_filled_with(1, Boxes::Chance, &dice, &turn),
+            16 + 32 + 75
+        );
+    }
+
+    #[test]
+    fn test_fill_order() {
+        let mut score_table = ScoreTable::with_rule_set(Variant::Yamb.rule_set());
+        let dice = Dice::try_from([1, 2, 3, 4, 6]).unwrap();
+        let turn = TurnContext::new(Play::DEFAULT_ROLLS_PER_TURN);
+        let (down, up, free, announce) = (0, 1, 2, 3);
+        assert!(score_table.can_fill(down, Boxes::Aces, &dice));
+        assert!(!score_table.can_fill(down, Boxes::Twos, &dice));
+        assert!(score_table.can_fill(up, Boxes::Yahtzee, &dice));
+        assert!(!score_table.can_fill(up, Boxes::Aces, &dice));
+        assert!(score_table.can_fill(free, Boxes::Max, &dice));
+        assert!(!score_table.can_fill(announce, Boxes::Max, &dice));
+        assert!(score_table
+            .confirm_dice(down, Boxes::Twos, &dice, &turn)
+            .is_err());
+
+        score_table
+            .confirm_dice(down, Boxes::Aces, &dice, &turn)
+            .unwrap();
+        score_table
+            .confirm_dice(up, Boxes::Yahtzee, &dice, &turn)
+            .unwrap();
+        assert!(score_table.can_fill(down, Boxes::Twos, &dice));
+        assert!(score_table.can_fill(up, Boxes::FourOfaAKind, &dice));
+
+        assert!(!score_table.must_announce());
+        assert!(!score_table.can_announce(free, Boxes::Max));
+        score_table.announce(announce, Boxes::Max).unwrap();
+        assert!(!score_table.can_fill(down, Boxes::Twos, &dice));
+        assert!(!score_table.can_fill(announce, Boxes::Min, &dice));
+        assert!(score_table.can_fill(announce, Boxes::Max, &dice));
+        score_table
+            .confirm_dice(announce, Boxes::Max, &dice, &turn)
+            .unwrap();
+        assert!(score_table.get_announced_box().is_none());
+        assert!(!score_table.can_announce(announce, Boxes::Max));
+    }
 }
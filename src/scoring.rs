@@ -1,14 +1,6 @@
-use crate::hand::Hand;
-use array_macro::array;
+use crate::dice::{Dice, Pip};
 use enum_iterator::Sequence;
-use std::collections::HashSet;
 use std::fmt;
-use std::iter::FromIterator;
-
-const FULL_HOUSE_SCORE: u32 = 25;
-const SMALL_STRAIGHT_SCORE: u32 = 30;
-const LARGE_STRAIGHT_SCORE: u32 = 40;
-const YAHTZEE_SCORE: u32 = 50;
 
 #[derive(PartialEq, Eq, Hash, Sequence, Clone, Copy)]
 pub enum Boxes {
@@ -18,13 +10,23 @@ pub enum Boxes {
     Fours,
     Fives,
     Sixes,
+    OnePair,
+    TwoPairs,
+    ThreePairs,
     ThreeOfaAKind,
     FourOfaAKind,
+    FiveOfaAKind,
     FullHouse,
+    Castle,
+    Tower,
     SmallStraight,
     LargeStraight,
+    FullStraight,
     Yahtzee,
+    DoubleYahtzee,
     Chance,
+    Max,
+    Min,
 }
 
 impl fmt::Display for Boxes {
@@ -36,162 +38,108 @@ impl fmt::Display for Boxes {
             Boxes::Fours => f.pad("Fours"),
             Boxes::Fives => f.pad("Fives"),
             Boxes::Sixes => f.pad("Sixes"),
+            Boxes::OnePair => f.pad("One pair"),
+            Boxes::TwoPairs => f.pad("Two pairs"),
+            Boxes::ThreePairs => f.pad("Three pairs"),
             Boxes::ThreeOfaAKind => f.pad("Three of a kind"),
             Boxes::FourOfaAKind => f.pad("Four of a kind"),
+            Boxes::FiveOfaAKind => f.pad("Five of a kind"),
             Boxes::FullHouse => f.pad("Full house"),
+            Boxes::Castle => f.pad("Castle"),
+            Boxes::Tower => f.pad("Tower"),
             Boxes::SmallStraight => f.pad("Small straight"),
             Boxes::LargeStraight => f.pad("Large straight"),
+            Boxes::FullStraight => f.pad("Full straight"),
             Boxes::Yahtzee => f.pad("Yahtzee"),
+            Boxes::DoubleYahtzee => f.pad("Double Yahtzee"),
             Boxes::Chance => f.pad("Chance"),
+            Boxes::Max => f.pad("Max"),
+            Boxes::Min => f.pad("Min"),
         }
     }
 }
 
-pub fn scoring(b: Boxes, dice: &[u32]) -> u32 {
-    if dice.len() > Hand::DICE_NUM {
-        panic!(
-            "your hand ({} dice) is larger than {} dice.",
-            dice.len(),
-            Hand::DICE_NUM
-        );
-    }
-    match b {
-        Boxes::Aces => aces(dice),
-        Boxes::Twos => twos(dice),
-        Boxes::Threes => threes(dice),
-        Boxes::Fours => fours(dice),
-        Boxes::Fives => fives(dice),
-        Boxes::Sixes => sixes(dice),
-        Boxes::ThreeOfaAKind => three_of_a_kind(dice),
-        Boxes::FourOfaAKind => four_of_a_kind(dice),
-        Boxes::FullHouse => full_house(dice),
-        Boxes::SmallStraight => small_straight(dice),
-        Boxes::LargeStraight => large_straight(dice),
-        Boxes::Yahtzee => yahtzee(dice),
-        Boxes::Chance => chance(dice),
-    }
+/* Distinct pips shared by at least `n` dice, highest first. */
+fn group_pips(dice: &Dice, n: usize) -> Vec<Pip> {
+    dice.groups()
+        .rev()
+        .filter(|&(.., count)| count >= n)
+        .map(|(pip, ..)| pip)
+        .collect()
 }
 
-fn aces(dice: &[u32]) -> u32 {
-    upper_section_scoring(dice, 1)
+pub fn groups_of(dice: &Dice, n: usize) -> Vec<u32> {
+    group_pips(dice, n).into_iter().map(|p| p.value()).collect()
 }
 
-fn twos(dice: &[u32]) -> u32 {
-    upper_section_scoring(dice, 2)
-}
-
-fn threes(dice: &[u32]) -> u32 {
-    upper_section_scoring(dice, 3)
-}
-
-fn fours(dice: &[u32]) -> u32 {
-    upper_section_scoring(dice, 4)
-}
-
-fn fives(dice: &[u32]) -> u32 {
-    upper_section_scoring(dice, 5)
+pub fn n_of_a_kind(dice: &Dice, n: u32) -> u32 {
+    if groups_of(dice, n as usize).is_empty() {
+        0
+    } else {
+        dice.sum()
+    }
 }
 
-fn sixes(dice: &[u32]) -> u32 {
-    upper_section_scoring(dice, 6)
+pub fn highest_n_of_a_kind(dice: &Dice, n: usize) -> Option<u32> {
+    groups_of(dice, n).first().copied()
 }
 
-fn n_of_a_kind(dice: &[u32], n: u32) -> u32 {
-    let n: usize = n as usize;
-    if dice.len() < n {
-        return 0;
+pub fn two_pairs(dice: &Dice) -> Option<(u32, u32)> {
+    match groups_of(dice, 2)[..] {
+        [high, low, ..] => Some((high, low)),
+        _ => None,
     }
-
-    for d in dice[0..=(Hand::DICE_NUM - n)].iter() {
-        if dice.iter().filter(|&x| x == d).count() >= n {
-            return dice.iter().sum();
-        }
-    }
-
-    0
 }
 
-fn three_of_a_kind(dice: &[u32]) -> u32 {
-    n_of_a_kind(dice, 3)
+pub fn contains_pips(dice: &Dice, pips: &[u32]) -> bool {
+    pips.iter().all(|&p| dice.iter().any(|d| d.value() == p))
 }
 
-fn four_of_a_kind(dice: &[u32]) -> u32 {
-    n_of_a_kind(dice, 4)
-}
-
-fn full_house(dice: &[u32]) -> u32 {
-    if dice.len() != Hand::DICE_NUM {
-        return 0;
-    }
+/* Pips of the highest `m` of a kind and the highest `n` of a kind among the rest. */
+pub fn m_and_n_of_a_kind(dice: &Dice, m: usize, n: usize) -> Option<(u32, u32)> {
+    let high = *group_pips(dice, m).first()?;
+    let low = *group_pips(&dice.without(high), n).first()?;
 
-    let mut sorted_dice: Vec<u32> = dice.to_vec();
-    sorted_dice.sort_unstable();
-    let init = sorted_dice[0];
-    let end = sorted_dice[Hand::DICE_NUM - 1];
-    let case0 = [init, init, init, end, end];
-    let case1 = [init, init, end, end, end];
-
-    if (init != end) && (sorted_dice == case0 || sorted_dice == case1) {
-        FULL_HOUSE_SCORE
-    } else {
-        0
-    }
+    Some((high.value(), low.value()))
 }
 
-fn small_straight(dice: &[u32]) -> u32 {
-    let unique_dice: HashSet<u32> = dice.iter().copied().collect();
-    let mut sorted_dice: Vec<u32> = Vec::from_iter(unique_dice);
-    sorted_dice.sort_unstable();
-
-    if sorted_dice.len() < 4 {
-        return 0;
-    }
-
-    for (i, d) in sorted_dice[0..=(sorted_dice.len() - 4)].iter().enumerate() {
-        if sorted_dice[i..(i + 4)] == array![i => d + (i as u32); 4] {
-            return SMALL_STRAIGHT_SCORE;
-        }
-    }
-
-    0
+pub fn is_full_house(dice: &Dice) -> bool {
+    m_and_n_of_a_kind(dice, 3, 2).is_some()
 }
 
-fn large_straight(dice: &[u32]) -> u32 {
-    if dice.len() != Hand::DICE_NUM {
-        return 0;
-    }
+pub fn has_straight(dice: &Dice, len: usize) -> bool {
+    let unique_dice: Vec<u32> = dice.groups().map(|(p, ..)| p.value()).collect();
 
-    let mut sorted_dice: Vec<u32> = dice.to_vec();
-    sorted_dice.sort_unstable();
-    if sorted_dice == array![i => sorted_dice[0] + (i as u32); 5] {
-        LARGE_STRAIGHT_SCORE
-    } else {
-        0
+    if unique_dice.len() < len {
+        return false;
     }
+
+    unique_dice
+        .windows(len)
+        .any(|w| w.iter().enumerate().all(|(i, &d)| d == w[0] + (i as u32)))
 }
 
-fn yahtzee(dice: &[u32]) -> u32 {
-    if dice.len() != Hand::DICE_NUM {
-        0
-    } else if dice == [dice[0]; Hand::DICE_NUM] {
-        YAHTZEE_SCORE
-    } else {
-        0
-    }
+pub fn is_yahtzee(dice: &Dice, dice_num: usize) -> bool {
+    dice.len() == dice_num && dice.groups().count() == 1
 }
 
-fn chance(dice: &[u32]) -> u32 {
-    dice.iter().sum()
+pub fn chance(dice: &Dice) -> u32 {
+    dice.sum()
 }
 
-fn upper_section_scoring(dice: &[u32], spots: u32) -> u32 {
-    (dice.iter().filter(|&d| *d == spots).count() as u32) * spots
+pub fn upper_section_scoring(dice: &Dice, spots: u32) -> u32 {
+    Pip::new(spots).map_or(0, |p| dice.count(p) as u32 * spots)
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::rule_set::{ClassicYahtzee, RuleSet};
     use crate::scoring::*;
 
+    fn scoring<const N: usize>(b: Boxes, dice: &[u32; N]) -> u32 {
+        ClassicYahtzee.score(b, &Dice::try_from(*dice).unwrap())
+    }
+
     #[test]
     fn upper_section_scoring_test() {
         let dice: [u32; 5] = [1, 3, 3, 3, 6];
@@ -243,7 +191,10 @@ mod tests {
     #[test]
     fn full_house_test() {
         let dice: [u32; 5] = [4, 3, 3, 4, 3];
-        assert_eq!(scoring(Boxes::FullHouse, &dice), FULL_HOUSE_SCORE);
+        assert_eq!(
+            scoring(Boxes::FullHouse, &dice),
+            ClassicYahtzee::FULL_HOUSE_SCORE
+        );
 
         let dice: [u32; 5] = [5, 5, 5, 5, 5];
         assert_eq!(scoring(Boxes::FullHouse, &dice), 0);
@@ -261,13 +212,19 @@ mod tests {
     #[test]
     fn small_straight_test() {
         let dice: [u32; 5] = [5, 3, 4, 4, 2];
-        assert_eq!(scoring(Boxes::SmallStraight, &dice), SMALL_STRAIGHT_SCORE);
+        assert_eq!(
+            scoring(Boxes::SmallStraight, &dice),
+            ClassicYahtzee::SMALL_STRAIGHT_SCORE
+        );
 
         let dice: [u32; 5] = [2, 4, 4, 6, 4];
         assert_eq!(scoring(Boxes::SmallStraight, &dice), 0);
 
         let dice: [u32; 4] = [5, 3, 4, 2];
-        assert_eq!(scoring(Boxes::SmallStraight, &dice), SMALL_STRAIGHT_SCORE);
+        assert_eq!(
+            scoring(Boxes::SmallStraight, &dice),
+            ClassicYahtzee::SMALL_STRAIGHT_SCORE
+        );
 
         let dice: [u32; 3] = [5, 3, 4];
         assert_eq!(scoring(Boxes::SmallStraight, &dice), 0);
@@ -279,7 +236,10 @@ mod tests {
     #[test]
     fn large_straight_test() {
         let dice: [u32; 5] = [5, 3, 1, 4, 2];
-        assert_eq!(scoring(Boxes::LargeStraight, &dice), LARGE_STRAIGHT_SCORE);
+        assert_eq!(
+            scoring(Boxes::LargeStraight, &dice),
+            ClassicYahtzee::LARGE_STRAIGHT_SCORE
+        );
 
         let dice: [u32; 5] = [2, 3, 4, 1, 4];
         assert_eq!(scoring(Boxes::LargeStraight, &dice), 0);
@@ -294,7 +254,10 @@ mod tests {
     #[test]
     fn yahtzee_test() {
         let dice: [u32; 5] = [4, 4, 4, 4, 4];
-        assert_eq!(scoring(Boxes::Yahtzee, &dice), YAHTZEE_SCORE);
+        assert_eq!(
+            scoring(Boxes::Yahtzee, &dice),
+            ClassicYahtzee::YAHTZEE_SCORE
+        );
 
         let dice: [u32; 5] = [2, 3, 5, 1, 4];
         assert_eq!(scoring(Boxes::Yahtzee, &dice), 0);
@@ -326,4 +289,37 @@ mod tests {
         let dice: [u32; 0] = [];
         assert_eq!(scoring(Boxes::Chance, &dice), 0);
     }
+
+    #[test]
+    fn highest_n_of_a_kind_test() {
+        let dice = Dice::try_from([2, 2, 5, 5, 5]).unwrap();
+        assert_eq!(highest_n_of_a_kind(&dice, 2), Some(5));
+        assert_eq!(highest_n_of_a_kind(&dice, 3), Some(5));
+        assert_eq!(highest_n_of_a_kind(&dice, 4), None);
+
+        let dice = Dice::try_from([6, 2, 2, 4, 4]).unwrap();
+        assert_eq!(highest_n_of_a_kind(&dice, 2), Some(4));
+        assert_eq!(two_pairs(&dice), Some((4, 2)));
+
+        let dice = Dice::try_from([3, 3, 3, 3, 1]).unwrap();
+        assert_eq!(two_pairs(&dice), None);
+
+        let dice = Dice::try_from([3, 3, 3, 2, 1, 1]).unwrap();
+        assert_eq!(groups_of(&dice, 2), vec![3, 1]);
+        assert_eq!(m_and_n_of_a_kind(&dice, 3, 2), Some((3, 1)));
+        assert_eq!(m_and_n_of_a_kind(&dice, 3, 3), None);
+        assert_eq!(n_of_a_kind(&dice, 3), 13);
+        assert_eq!(n_of_a_kind(&dice, 4), 0);
+    }
+
+    #[test]
+    fn has_straight_test() {
+        let dice = Dice::try_from([6, 3, 4, 4, 5]).unwrap();
+        assert!(has_straight(&dice, 4));
+        assert!(!has_straight(&dice, 5));
+
+        let dice = Dice::try_from([1, 2, 3, 5, 6]).unwrap();
+        assert!(has_straight(&dice, 3));
+        assert!(!has_straight(&dice, 4));
+    }
 }
@@ -0,0 +1,496 @@
+use crate::dice::{Dice, Pip};
+use crate::play::{Play, TurnContext};
+use crate::rule_set::{ClassicYahtzee, RuleSet};
+use crate::score_table::ScoreColumn;
+use crate::scoring::Boxes;
+use anyhow::{Context, Result};
+use rand::Rng;
+use std::collections::HashMap;
+
+const NUM_BOXES: usize = 13;
+const DICE_NUM: usize = 5;
+const FACES: u32 = 6;
+const UPPER_CAP: u32 = ClassicYahtzee::BONUS_THRESHOLD;
+const MAX_SCORE: usize = ClassicYahtzee::YAHTZEE_SCORE as usize;
+
+/* Expected points to come from filling a box with a score, NaN until needed */
+type Futures = [[f64; MAX_SCORE + 1]; NUM_BOXES];
+
+/* What decides the rest of a solitaire game of Yahtzee at the start of a turn */
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub struct TurnState {
+    /* One bit per box of ClassicYahtzee in the order of its boxes */
+    filled: u16,
+    /* The upper section subtotal, which matters only up to the bonus threshold */
+    upper: u32,
+    /* Whether the Yahtzee box holds 50, which makes later Yahtzees jokers */
+    has_yahtzee: bool,
+}
+
+impl TurnState {
+    pub fn from_column(column: &ScoreColumn) -> Self {
+        let filled = ClassicYahtzee
+            .boxes()
+            .iter()
+            .enumerate()
+            .filter(|&(.., &b)| column.has_score_in(b))
+            .fold(0, |filled, (i, ..)| filled | 1 << i);
+        Self {
+            filled,
+            upper: column.get_total_upper_score().min(UPPER_CAP),
+            has_yahtzee: column.get_score(Boxes::Yahtzee) == Some(ClassicYahtzee::YAHTZEE_SCORE),
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.filled.count_ones() as usize == NUM_BOXES
+    }
+
+    fn is_open(&self, i: usize) -> bool {
+        self.filled & 1 << i == 0
+    }
+
+    fn index(&self) -> usize {
+        ((self.filled as usize) * (UPPER_CAP as usize + 1) + self.upper as usize) * 2
+            + usize::from(self.has_yahtzee)
+    }
+
+    /* Bits of the boxes which the dice can fill under the joker rules of ClassicYahtzee */
+    fn fillable_boxes(&self, dice: &Dice) -> u16 {
+        let open = !self.filled & ((1 << NUM_BOXES) - 1);
+        if !self.is_joker(dice) {
+            return open;
+        }
+
+        let forced = dice.iter().next().map_or(0, |p| p.value() as usize - 1);
+        if self.is_open(forced) {
+            return 1 << forced;
+        }
+        let upper = (1 << ClassicYahtzee::BONUS_TARGETS.len()) - 1;
+        if open & !upper != 0 {
+            open & !upper
+        } else {
+            open & upper
+        }
+    }
+
+    fn is_joker(&self, dice: &Dice) -> bool {
+        self.has_yahtzee && dice.groups().count() == 1
+    }
+
+    /* The state after filling the box with the score and the points gained with the upper bonus */
+    fn fill(&self, i: usize, score: u32) -> (Self, u32) {
+        let b = ClassicYahtzee.boxes()[i];
+        let mut next = *self;
+        let mut gained = score;
+        next.filled |= 1 << i;
+        if i < ClassicYahtzee::BONUS_TARGETS.len() {
+            next.upper = (self.upper + score).min(UPPER_CAP);
+            if self.upper < UPPER_CAP && next.upper == UPPER_CAP {
+                gained += ClassicYahtzee::BONUS_POINT;
+            }
+        }
+        if b == Boxes::Yahtzee {
+            next.has_yahtzee = score == ClassicYahtzee::YAHTZEE_SCORE;
+        }
+        (next, gained)
+    }
+}
+
+/* Expected values within a turn, where index n is for the rolls with n rerolls left */
+struct TurnValues {
+    rolls: Vec<Vec<f64>>,
+    keeps: Vec<Vec<f64>>,
+}
+
+/* Exact expected-value-maximizing strategy for solitaire Yahtzee with five six-sided dice */
+pub struct Solver {
+    rolls: Vec<Dice>,
+    roll_index: HashMap<Dice, usize>,
+    roll_probs: Vec<f64>,
+    /* Every hold of zero to five dice, larger holds first */
+    keeps: Vec<Dice>,
+    keep_index: HashMap<Dice, usize>,
+    /* The holds with one more die for each face, unused for holds of all the dice */
+    keep_children: Vec<[usize; FACES as usize]>,
+    /* The holds with one less die for each face, or the hold itself without such a die */
+    keep_parents: Vec<[usize; FACES as usize]>,
+    /* Distinct holds which each roll can keep */
+    roll_keeps: Vec<Vec<usize>>,
+    scores: Vec<[u32; NUM_BOXES]>,
+    joker_scores: Vec<[u32; NUM_BOXES]>,
+    /* Expected points still to come at the start of a turn, NaN until solved */
+    values: Vec<f64>,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        let pips: Vec<_> = Pip::all().take(FACES as usize).collect();
+        let rolls = Dice::multisets(DICE_NUM, &pips);
+        let roll_index: HashMap<_, _> = rolls.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+        let roll_probs = rolls.iter().map(Self::probability).collect();
+
+        let keeps: Vec<_> = (0..=DICE_NUM)
+            .rev()
+            .flat_map(|n| Dice::multisets(n, &pips))
+            .collect();
+        let keep_index: HashMap<_, _> = keeps.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+        let keep_children: Vec<_> = keeps
+            .iter()
+            .map(|keep| {
+                let mut children = [0; FACES as usize];
+                if keep.len() < DICE_NUM {
+                    for (child, &p) in children.iter_mut().zip(&pips) {
+                        let mut dice = *keep;
+                        dice.push(p).unwrap();
+                        *child = keep_index[&dice];
+                    }
+                }
+                children
+            })
+            .collect();
+        let mut keep_parents: Vec<_> = (0..keeps.len()).map(|k| [k; FACES as usize]).collect();
+        for (k, children) in keep_children.iter().enumerate() {
+            if keeps[k].len() < DICE_NUM {
+                for (face, &child) in children.iter().enumerate() {
+                    keep_parents[child][face] = k;
+                }
+            }
+        }
+        let roll_keeps = rolls
+            .iter()
+            .map(|roll| {
+                keeps
+                    .iter()
+                    .enumerate()
+                    .filter(|(.., keep)| pips.iter().all(|&p| keep.count(p) <= roll.count(p)))
+                    .map(|(i, ..)| i)
+                    .collect()
+            })
+            .collect();
+
+        let rule = ClassicYahtzee;
+        let scores: Vec<[u32; NUM_BOXES]> = rolls
+            .iter()
+            .map(|roll| {
+                let mut scores = [0; NUM_BOXES];
+                for (i, &b) in rule.boxes().iter().enumerate() {
+                    scores[i] = rule.look_up_score(b, roll);
+                }
+                scores
+            })
+            .collect();
+        /* Full house and straights score their full value when a Yahtzee is a joker */
+        let joker_scores = scores
+            .iter()
+            .map(|&scores| {
+                let mut joker = scores;
+                for (i, &b) in rule.boxes().iter().enumerate() {
+                    match b {
+                        Boxes::FullHouse => joker[i] = ClassicYahtzee::FULL_HOUSE_SCORE,
+                        Boxes::SmallStraight => joker[i] = ClassicYahtzee::SMALL_STRAIGHT_SCORE,
+                        Boxes::LargeStraight => joker[i] = ClassicYahtzee::LARGE_STRAIGHT_SCORE,
+                        _ => (),
+                    }
+                }
+                joker
+            })
+            .collect();
+
+        Self {
+            rolls,
+            roll_index,
+            roll_probs,
+            keeps,
+            keep_index,
+            keep_children,
+            keep_parents,
+            roll_keeps,
+            scores,
+            joker_scores,
+            values: vec![f64::NAN; (1 << NUM_BOXES) * (UPPER_CAP as usize + 1) * 2],
+        }
+    }
+
+    /* The chance of rolling the dice at once */
+    fn probability(dice: &Dice) -> f64 {
+        let factorial = |n: usize| (1..=n).product::<usize>() as f64;
+        let arrangements = dice
+            .groups()
+            .fold(factorial(dice.len()), |a, (.., c)| a / factorial(c));
+        arrangements / f64::from(FACES).powi(dice.len() as i32)
+    }
+
+    /* Expected points still to come from the start of a turn in the state */
+    pub fn expected_score(&mut self, state: TurnState) -> f64 {
+        if state.is_over() {
+            return 0.0;
+        }
+        let value = self.values[state.index()];
+        if !value.is_nan() {
+            return value;
+        }
+
+        let turn = self.turn_values(state, 2);
+        let value = turn.rolls[2]
+            .iter()
+            .zip(&self.roll_probs)
+            .map(|(v, p)| v * p)
+            .sum();
+        self.values[state.index()] = value;
+        value
+    }
+
+    /* Expected final score of the column's game before the first roll of a turn */
+    pub fn expected_final_score(&mut self, column: &ScoreColumn) -> f64 {
+        f64::from(column.get_total_score()) + self.expected_score(TurnState::from_column(column))
+    }
+
+    /* The dice to hold and the expected points to come; holding all means scoring now */
+    pub fn best_hold(&mut self, state: TurnState, dice: &Dice, rerolls: usize) -> (Dice, f64) {
+        let Some(&r) = self.roll_index.get(dice) else {
+            return (*dice, self.expected_score(state));
+        };
+        if rerolls == 0 {
+            let value = self.turn_values(state, 0).rolls[0][r];
+            return (*dice, value);
+        }
+
+        let turn = self.turn_values(state, rerolls);
+        let values = &turn.keeps[rerolls];
+        let best = self.roll_keeps[r]
+            .iter()
+            .copied()
+            .max_by(|&a, &b| values[a].total_cmp(&values[b]))
+            .unwrap();
+        (self.keeps[best], values[best])
+    }
+
+    /* The expected points from holding the dice and rolling the rest, or from scoring them all */
+    pub fn hold_value(&mut self, state: TurnState, hold: &Dice, rerolls: usize) -> Option<f64> {
+        if hold.len() == DICE_NUM {
+            return self.best_box(state, hold).map(|(.., value)| value);
+        }
+        let &k = self.keep_index.get(hold)?;
+        if rerolls == 0 {
+            return None;
+        }
+        Some(self.turn_values(state, rerolls).keeps[rerolls][k])
+    }
+
+    /* The box to score the dice in and the expected points to come including the box */
+    pub fn best_box(&mut self, state: TurnState, dice: &Dice) -> Option<(Boxes, f64)> {
+        self.box_values(state, dice)
+            .into_iter()
+            .reduce(|best, (b, value)| if value > best.1 { (b, value) } else { best })
+    }
+
+    /* Each box which the dice can fill with the expected points to come including the box */
+    pub fn box_values(&mut self, state: TurnState, dice: &Dice) -> Vec<(Boxes, f64)> {
+        let Some(&r) = self.roll_index.get(dice) else {
+            return vec![];
+        };
+        let (scores, bonus) = self.roll_scores(state, r);
+        let fillable = state.fillable_boxes(dice);
+        (0..NUM_BOXES)
+            .filter(|&i| fillable & 1 << i != 0)
+            .map(|i| {
+                let (next, gained) = state.fill(i, scores[i]);
+                let value = bonus + f64::from(gained) + self.expected_score(next);
+                (ClassicYahtzee.boxes()[i], value)
+            })
+            .collect()
+    }
+
+    /* Plays a whole game of ClassicYahtzee by the strategy and returns its final score */
+    pub fn play_out<R: Rng>(&mut self, rng: &mut R) -> Result<u32> {
+        let mut column = ScoreColumn::new();
+        let turn = TurnContext::new(Play::DEFAULT_ROLLS_PER_TURN);
+        while !column.has_all_scores() {
+            let state = TurnState::from_column(&column);
+            let mut dice = Dice::default();
+            for rerolls in (0..turn.roll_count).rev() {
+                while dice.len() < DICE_NUM {
+                    dice.push(Pip::new(rng.gen_range(1..=FACES))?)?;
+                }
+                if rerolls > 0 {
+                    dice = self.best_hold(state, &dice, rerolls).0;
+                }
+            }
+            let (b, ..) = self
+                .best_box(state, &dice)
+                .context("no box is left for the dice")?;
+            column.confirm_dice(b, &dice, &turn)?;
+        }
+        Ok(column.get_total_score())
+    }
+
+    /* The score of the roll in each box and the Yahtzee bonus it earns */
+    fn roll_scores(&self, state: TurnState, r: usize) -> ([u32; NUM_BOXES], f64) {
+        if state.is_joker(&self.rolls[r]) {
+            let bonus = f64::from(ClassicYahtzee::YAHTZEE_BONUS_POINT);
+            (self.joker_scores[r], bonus)
+        } else {
+            (self.scores[r], 0.0)
+        }
+    }
+
+    fn best_box_of_roll(
+        &mut self,
+        state: TurnState,
+        r: usize,
+        futures: &mut Futures,
+    ) -> Option<(usize, f64)> {
+        let (scores, bonus) = self.roll_scores(state, r);
+        let fillable = state.fillable_boxes(&self.rolls[r]);
+        let mut best: Option<(usize, f64)> = None;
+        for i in (0..NUM_BOXES).filter(|&i| fillable & 1 << i != 0) {
+            /* The state after filling depends only on the box and its score */
+            let future = &mut futures[i][scores[i] as usize];
+            if future.is_nan() {
+                let (next, gained) = state.fill(i, scores[i]);
+                *future = f64::from(gained) + self.expected_score(next);
+            }
+            let value = bonus + *future;
+            if best.is_none_or(|(.., v)| value > v) {
+                best = Some((i, value));
+            }
+        }
+        best
+    }
+
+    fn turn_values(&mut self, state: TurnState, rerolls: usize) -> TurnValues {
+        let mut futures = [[f64::NAN; MAX_SCORE + 1]; NUM_BOXES];
+        let last: Vec<f64> = (0..self.rolls.len())
+            .map(|r| {
+                self.best_box_of_roll(state, r, &mut futures)
+                    .map_or(0.0, |(.., v)| v)
+            })
+            .collect();
+
+        let mut turn = TurnValues {
+            rolls: vec![last],
+            keeps: vec![vec![]],
+        };
+        for n in 1..=rerolls {
+            let rolls = &turn.rolls[n - 1];
+            let mut keeps = vec![0.0; self.keeps.len()];
+            /* Holds of all the dice come first in the order of the rolls, then smaller ones */
+            keeps[..rolls.len()].copy_from_slice(rolls);
+            for k in rolls.len()..self.keeps.len() {
+                keeps[k] =
+                    self.keep_children[k].iter().map(|&c| keeps[c]).sum::<f64>() / f64::from(FACES);
+            }
+            /* The best of each hold and the holds within it, smaller holds first */
+            let mut best = keeps.clone();
+            for k in (0..self.keeps.len()).rev() {
+                for &parent in &self.keep_parents[k] {
+                    if best[parent] > best[k] {
+                        best[k] = best[parent];
+                    }
+                }
+            }
+            best.truncate(self.rolls.len());
+            turn.rolls.push(best);
+            turn.keeps.push(keeps);
+        }
+        turn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::*;
+
+    fn only_open(b: Boxes) -> TurnState {
+        let i = ClassicYahtzee.boxes().iter().position(|&x| x == b).unwrap();
+        TurnState {
+            filled: ((1 << NUM_BOXES) - 1) & !(1 << i),
+            ..TurnState::default()
+        }
+    }
+
+    #[test]
+    fn last_box_test() {
+        let mut solver = Solver::new();
+        /* 50 times the chance of a Yahtzee within three rolls */
+        let yahtzee = solver.expected_score(only_open(Boxes::Yahtzee));
+        assert!((yahtzee - 50.0 * 0.046_029_6).abs() < 1e-4, "{}", yahtzee);
+        let chance = solver.expected_score(only_open(Boxes::Chance));
+        assert!((chance - 70.0 / 3.0).abs() < 1e-9, "{}", chance);
+    }
+
+    #[test]
+    fn best_hold_test() {
+        let mut solver = Solver::new();
+        let state = only_open(Boxes::Yahtzee);
+        let dice = Dice::try_from([6, 2, 6, 3, 6]).unwrap();
+        let (hold, ..) = solver.best_hold(state, &dice, 2);
+        assert_eq!(hold, Dice::try_from([6, 6, 6]).unwrap());
+
+        let state = only_open(Boxes::Chance);
+        let dice = Dice::try_from([6, 5, 1, 2, 4]).unwrap();
+        let (hold, value) = solver.best_hold(state, &dice, 1);
+        assert_eq!(hold, Dice::try_from([6, 5, 4]).unwrap());
+        assert!((value - 15.0 - 2.0 * 3.5).abs() < 1e-9, "{}", value);
+        assert_eq!(solver.hold_value(state, &hold, 1), Some(value));
+
+        /* Holding every die scores them at once */
+        let all = solver.hold_value(state, &dice, 1).unwrap();
+        assert!((all - 18.0).abs() < 1e-9, "{}", all);
+        let single = solver.hold_value(state, &Dice::try_from([6]).unwrap(), 1);
+        assert!(single.is_some_and(|v| (v - 6.0 - 4.0 * 3.5).abs() < 1e-9));
+        assert_eq!(
+            solver.hold_value(state, &Dice::try_from([6]).unwrap(), 0),
+            None
+        );
+    }
+
+    #[test]
+    fn from_column_test() {
+        let mut column = ScoreColumn::new();
+        for &b in ClassicYahtzee.boxes() {
+            match b {
+                Boxes::Chance => (),
+                Boxes::Yahtzee => column.confirm_score(b, 50).unwrap(),
+                _ => column.confirm_score(b, 10).unwrap(),
+            }
+        }
+        let state = TurnState::from_column(&column);
+        let expected_state = TurnState {
+            upper: 60,
+            has_yahtzee: true,
+            ..only_open(Boxes::Chance)
+        };
+        assert_eq!(state, expected_state);
+        assert!(!state.is_over());
+
+        /* A Yahtzee for Chance still earns a bonus chip */
+        let mut solver = Solver::new();
+        let expected = solver.expected_final_score(&column);
+        assert!(expected > f64::from(column.get_total_score()) + 70.0 / 3.0);
+    }
+
+    #[test]
+    fn best_box_test() {
+        let mut solver = Solver::new();
+        let yahtzee = Dice::try_from([4, 4, 4, 4, 4]).unwrap();
+        let state = only_open(Boxes::Chance);
+        assert!(solver.best_box(state, &yahtzee) == Some((Boxes::Chance, 20.0)));
+        assert!(solver.box_values(state, &yahtzee) == vec![(Boxes::Chance, 20.0)]);
+
+        /* A joker must go to the matching upper box while it is open */
+        let mut state = only_open(Boxes::Fours);
+        state.filled &= !(1 << 12);
+        state.has_yahtzee = true;
+        let (b, value) = solver.best_box(state, &yahtzee).unwrap();
+        assert!(b == Boxes::Fours);
+        assert!(value > 120.0);
+    }
+}
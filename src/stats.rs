@@ -0,0 +1,401 @@
+use crate::hand::{DieSpec, Hand};
+use crate::scoring::is_yahtzee;
+use anyhow::{bail, ensure, Context, Result};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StatsError {
+    #[error("Line {0} of {1} is broken")]
+    BrokenLine(usize, String),
+}
+
+/* Tally of the dice settled by the random source for one kind of die */
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RollTally {
+    pub rolls: u64,
+    pub yahtzees: u64,
+    /* Counts in the order of the faces of the die */
+    pub faces: Vec<u64>,
+}
+
+impl RollTally {
+    fn new(die: &DieSpec) -> Self {
+        Self {
+            rolls: 0,
+            yahtzees: 0,
+            faces: vec![0; die.faces.len()],
+        }
+    }
+
+    pub fn get_num_dice(&self) -> u64 {
+        self.faces.iter().sum()
+    }
+
+    /* Pearson's goodness-of-fit test against a fair die */
+    pub fn chi_square(&self) -> Option<ChiSquare> {
+        let num_faces = self.faces.len();
+        let expected = self.get_num_dice() as f64 / num_faces as f64;
+        /* The approximation is unreliable unless every face is expected at least 5 times */
+        if num_faces < 2 || expected < 5.0 {
+            return None;
+        }
+
+        let statistic = self
+            .faces
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum();
+        let df = num_faces - 1;
+        Some(ChiSquare {
+            statistic,
+            df,
+            p_value: upper_regularized_gamma(df as f64 / 2.0, statistic / 2.0),
+        })
+    }
+
+    pub fn report_lines(&self, die: &DieSpec) -> Vec<String> {
+        let num_dice = self.get_num_dice();
+        let share = |n: u64, total: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                n as f64 * 100.0 / total as f64
+            }
+        };
+
+        let mut lines = vec![
+            format!("Rolls: {}  Dice: {}", self.rolls, num_dice),
+            String::new(),
+        ];
+        lines.extend(die.faces.iter().zip(&self.faces).map(|(face, &count)| {
+            format!(
+                "{:>4}: {:>7} ({:5.2}%)",
+                face.label,
+                count,
+                share(count, num_dice)
+            )
+        }));
+        lines.push(String::new());
+        lines.push(format!(
+            "Yahtzees: {} ({:.2}% of rolls)",
+            self.yahtzees,
+            share(self.yahtzees, self.rolls)
+        ));
+        match self.chi_square() {
+            Some(chi) => {
+                lines.push(format!(
+                    "Chi-square: {:.2} (df {}), p = {:.3}",
+                    chi.statistic, chi.df, chi.p_value
+                ));
+                lines.push(String::from(if chi.is_suspicious() {
+                    "The dice may be biased"
+                } else {
+                    "Consistent with fair dice"
+                }));
+            }
+            None => lines.push(String::from("Too few dice for a chi-square test")),
+        }
+        lines
+    }
+}
+
+pub struct ChiSquare {
+    pub statistic: f64,
+    pub df: usize,
+    pub p_value: f64,
+}
+
+impl ChiSquare {
+    pub const SIGNIFICANCE_LEVEL: f64 = 0.01;
+
+    pub fn is_suspicious(&self) -> bool {
+        self.p_value < Self::SIGNIFICANCE_LEVEL
+    }
+}
+
+/* Tallies for every kind of die in the order of DieSpec::ALL */
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RollStats {
+    tallies: Vec<RollTally>,
+}
+
+impl Default for RollStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RollStats {
+    pub fn new() -> Self {
+        Self {
+            tallies: DieSpec::ALL.iter().map(|&d| RollTally::new(d)).collect(),
+        }
+    }
+
+    fn index_of(die: &DieSpec) -> Option<usize> {
+        DieSpec::ALL.iter().position(|&d| d == die)
+    }
+
+    pub fn get_tally(&self, die: &DieSpec) -> Option<&RollTally> {
+        Self::index_of(die).map(|i| &self.tallies[i])
+    }
+
+    /* Records the dice which the last roll filled the hand with */
+    pub fn record(&mut self, hand: &Hand) -> Result<()> {
+        let die = hand.get_die_spec();
+        let Some(tally) = Self::index_of(die).map(|i| &mut self.tallies[i]) else {
+            return Ok(());
+        };
+
+        for pip in hand.get_rolled_pips() {
+            if let Some(i) = die.faces.iter().position(|f| f.value == pip.value()) {
+                tally.faces[i] += 1;
+            }
+        }
+        tally.rolls += 1;
+        if is_yahtzee(&hand.get_dice()?, hand.get_dice_num()) {
+            tally.yahtzees += 1;
+        }
+        Ok(())
+    }
+
+    pub fn report(&self) -> String {
+        let mut lines = vec![];
+        for (die, tally) in DieSpec::ALL.iter().zip(&self.tallies) {
+            if tally.rolls == 0 {
+                continue;
+            }
+            lines.push(format!("[{}]", die));
+            lines.extend(tally.report_lines(die));
+            lines.push(String::new());
+        }
+        if lines.is_empty() {
+            lines.push(String::from("No dice have been rolled yet."));
+        }
+        lines.join("\n")
+    }
+}
+
+/* One line per kind of die in the form of "name<TAB>rolls<TAB>yahtzees<TAB>counts..." */
+pub struct StatsFile {
+    path: PathBuf,
+}
+
+impl Default for StatsFile {
+    fn default() -> Self {
+        let dir = std::env::var_os("HOME").map_or_else(PathBuf::new, PathBuf::from);
+        Self::new(dir.join(".yahtzee_stats"))
+    }
+}
+
+impl StatsFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<RollStats> {
+        let mut stats = RollStats::new();
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(stats),
+            Err(e) => {
+                return Err(e).with_context(|| format!("could not read {}", self.path.display()))
+            }
+        };
+
+        /* A line which cannot be read fails the whole file so that it is not saved over */
+        for (n, line) in text.lines().enumerate() {
+            let broken = || StatsError::BrokenLine(n + 1, self.path.display().to_string());
+            let fields: Vec<_> = line.split('\t').collect();
+            let [name, rolls, yahtzees, faces] = fields[..] else {
+                bail!(broken());
+            };
+            let i = DieSpec::ALL
+                .iter()
+                .position(|d| d.name == name)
+                .with_context(broken)?;
+            let faces: Vec<u64> = faces
+                .split(' ')
+                .map(|c| c.parse())
+                .collect::<Result<_, _>>()
+                .with_context(broken)?;
+            ensure!(faces.len() == DieSpec::ALL[i].faces.len(), broken());
+            stats.tallies[i] = RollTally {
+                rolls: rolls.parse().with_context(broken)?,
+                yahtzees: yahtzees.parse().with_context(broken)?,
+                faces,
+            };
+        }
+        Ok(stats)
+    }
+
+    pub fn save(&self, stats: &RollStats) -> Result<()> {
+        let text: String = DieSpec::ALL
+            .iter()
+            .zip(&stats.tallies)
+            .map(|(die, tally)| {
+                let faces: Vec<_> = tally.faces.iter().map(|c| c.to_string()).collect();
+                format!(
+                    "{}\t{}\t{}\t{}\n",
+                    die.name,
+                    tally.rolls,
+                    tally.yahtzees,
+                    faces.join(" ")
+                )
+            })
+            .collect();
+        fs::write(&self.path, text)
+            .with_context(|| format!("could not write {}", self.path.display()))
+    }
+}
+
+fn ln_gamma(x: f64) -> f64 {
+    /* Lanczos approximation (g = 7, n = 9) */
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum: f64 = COEFFICIENTS[0]
+        + COEFFICIENTS[1..]
+            .iter()
+            .enumerate()
+            .map(|(i, c)| c / (x + i as f64 + 1.0))
+            .sum::<f64>();
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/* Q(a, x), which gives the p-value of a chi-square statistic 2x with 2a degrees of freedom */
+fn upper_regularized_gamma(a: f64, x: f64) -> f64 {
+    const EPSILON: f64 = 1e-12;
+    const MAX_ITERATIONS: usize = 500;
+
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefix = (a * x.ln() - x - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        /* The series of the lower function converges fast here */
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..MAX_ITERATIONS {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - prefix * sum).clamp(0.0, 1.0)
+    } else {
+        /* Lentz's method for the continued fraction of the upper function */
+        let tiny = f64::MIN_POSITIVE / EPSILON;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for n in 1..MAX_ITERATIONS {
+            let an = -(n as f64) * (n as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        (prefix * h).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hand::ManualDice;
+    use crate::stats::*;
+
+    #[test]
+    fn chi_square_test() {
+        /* The critical values at the 5% level */
+        assert!((upper_regularized_gamma(0.5, 3.841 / 2.0) - 0.05).abs() < 1e-3);
+        assert!((upper_regularized_gamma(2.5, 11.070 / 2.0) - 0.05).abs() < 1e-3);
+        assert!((upper_regularized_gamma(3.5, 14.067 / 2.0) - 0.05).abs() < 1e-3);
+        assert_eq!(upper_regularized_gamma(2.5, 0.0), 1.0);
+
+        let mut tally = RollTally::new(&DieSpec::D6);
+        assert!(tally.chi_square().is_none());
+
+        tally.faces = vec![100; 6];
+        let chi = tally.chi_square().unwrap();
+        assert_eq!(chi.statistic, 0.0);
+        assert_eq!(chi.df, 5);
+        assert!(!chi.is_suspicious());
+
+        tally.faces = vec![100, 100, 100, 100, 100, 200];
+        assert!(tally.chi_square().unwrap().is_suspicious());
+    }
+
+    #[test]
+    fn record_test() {
+        let mut stats = RollStats::new();
+        let mut hand = Hand::default();
+        hand.reroll_dice(&mut ManualDice::new(vec![2, 2, 2, 2, 2], &DieSpec::D6).unwrap())
+            .unwrap();
+        stats.record(&hand).unwrap();
+
+        hand.hold(0, true).unwrap();
+        hand.hold(1, true).unwrap();
+        hand.reroll_dice(&mut ManualDice::new(vec![1, 6, 6], &DieSpec::D6).unwrap())
+            .unwrap();
+        stats.record(&hand).unwrap();
+
+        let tally = stats.get_tally(&DieSpec::D6).unwrap();
+        assert_eq!(tally.rolls, 2);
+        assert_eq!(tally.yahtzees, 1);
+        assert_eq!(tally.faces, vec![1, 5, 0, 0, 0, 2]);
+        assert_eq!(stats.get_tally(&DieSpec::D8).unwrap().rolls, 0);
+    }
+
+    #[test]
+    fn stats_file_test() {
+        let path = std::env::temp_dir().join(format!("yahtzee_stats_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let file = StatsFile::new(path.clone());
+        assert_eq!(file.load().unwrap(), RollStats::new());
+
+        let mut stats = RollStats::new();
+        let mut hand = Hand::with_die_spec(Hand::DEFAULT_DICE_NUM, &DieSpec::D8).unwrap();
+        for _ in 0..10 {
+            hand.reroll_dice(&mut rand::thread_rng()).unwrap();
+            stats.record(&hand).unwrap();
+        }
+        file.save(&stats).unwrap();
+        assert_eq!(file.load().unwrap(), stats);
+
+        let text = fs::read_to_string(&path).unwrap();
+        fs::write(&path, text.replacen("\t", "\tx", 1)).unwrap();
+        assert!(file.load().is_err());
+        fs::write(&path, format!("{}Unknown\t1\t0\t5\n", text)).unwrap();
+        assert!(file.load().is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
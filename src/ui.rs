@@ -1,21 +1,26 @@
 use crate::app::{
-    App, AppState, AppStateError, NumPlayersSelection, PlayCursorPos, StartMenuSelection,
-    HIGHEST_PLAYER_ID, LOWEST_PLAYER_ID,
+    App, AppState, AppStateError, NumPlayersSelection, OptionsSelection, PlayCursorPos,
+    RuleSetSelection, SeatsSelection, StartMenuSelection, HIGHEST_PLAYER_ID, LOWEST_PLAYER_ID,
 };
 use crate::assets;
-use crate::hand::{Hand, HandOpError};
-use crate::play::PlayPhase;
-use crate::score_table::ScoreTable;
-use crate::scoring::{scoring, Boxes};
+use crate::bot::{Difficulty, Seat};
+use crate::dice::{Dice, Pip};
+use crate::fair::TranscriptFile;
+use crate::hand::DieSpec;
+use crate::play::{Play, PlayPhase, TurnContext};
+use crate::review::Choice;
+use crate::rule_set::{RuleSet, Variant};
+use crate::scoring::Boxes;
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
     Frame,
 };
+use std::rc::Rc;
 
-const DICE_KINDS: usize = 6;
+const DICE_KINDS: usize = 8;
 const DICE_STR_HEIGHT: usize = 3;
 
 #[rustfmt::skip]
@@ -51,6 +56,16 @@ const DICE_STR: [[&str; DICE_STR_HEIGHT]; DICE_KINDS] = [
         " *   * ",
         " *   * ",
     ],
+    [
+        " *   * ",
+        " * * * ",
+        " *   * ",
+    ],
+    [
+        " * * * ",
+        " *   * ",
+        " * * * ",
+    ],
 ];
 
 const DICE_STR_WIDTH: usize = {
@@ -73,6 +88,12 @@ const DICE_STR_WIDTH: usize = {
 };
 const BOXES_CELL_WIDTH: usize = 20;
 const SCORE_CELL_WIDTH: usize = 11;
+const COLUMN_CELL_WIDTH: usize = 7;
+const ENTRIES_CELL_WIDTH: usize = 15;
+
+/* Wide enough for the longest box name with the chance and the score */
+const ODDS_BOX_WIDTH: u16 = 15;
+const ODDS_PANEL_WIDTH: u16 = ODDS_BOX_WIDTH + 6 + 5 + 2 + 2;
 
 const FRAME_MARGIN: u16 = 1;
 
@@ -81,13 +102,20 @@ const SELECTION_MARGIN: u16 = 1;
 const DICE_MARGIN: u16 = 1;
 const HAND_MARGIN: u16 = 1;
 const DUST_MARGIN: u16 = 1;
+/* Between the dice and the borders of the blocks around them */
+const PLAYING_GROUND_MARGIN: u16 = 2;
 
 pub fn draw_ui(f: &mut Frame, app: &App) {
     match app.get_state() {
         AppState::StartMenu(..) => draw_start_menu(f, app),
+        AppState::Options(..) => draw_options(f, app),
+        AppState::Statistics(..) => draw_statistics(f, app),
+        AppState::SelectRuleSet(..) => draw_select_rule_set(f, app),
         AppState::SelectNumPlayers(..) => draw_select_number_of_players(f, app),
+        AppState::SelectSeats(..) => draw_select_seats(f, app),
         AppState::Play(..) => draw_play_ui(f, app),
         AppState::Result => draw_result_ui(f, app),
+        AppState::Review(..) => draw_review_ui(f, app),
     }
 }
 
@@ -96,11 +124,81 @@ fn draw_start_menu(f: &mut Frame, app: &App) {
     draw_start_menu_selections(f, app, chunk);
 }
 
+fn draw_options(f: &mut Frame, app: &App) {
+    let chunk = drwa_logo_and_frame(f);
+    draw_selections_for_options(f, app, chunk);
+}
+
+fn draw_statistics(f: &mut Frame, app: &App) {
+    let AppState::Statistics(die) = app.get_state() else {
+        panic!("Unexpected state")
+    };
+
+    let block = Block::default()
+        .title(format!("Statistics: < {} >", die))
+        .borders(Borders::ALL);
+    f.render_widget(block, f.size());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .margin(FRAME_MARGIN)
+        .split(f.size());
+    let tally_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[0]);
+
+    for (title, stats, chunk) in [
+        ("This session", Ok(app.get_session_stats()), tally_chunks[0]),
+        ("Lifetime", app.get_lifetime_stats(), tally_chunks[1]),
+    ] {
+        let lines: Vec<_> = match stats {
+            Ok(stats) => stats
+                .get_tally(die)
+                .map(|tally| tally.report_lines(die))
+                .unwrap_or_default(),
+            Err(e) => vec![
+                String::from("Could not load the statistics:"),
+                format!("{:#}", e),
+                String::new(),
+                String::from("They are not saved until the file is fixed"),
+            ],
+        };
+        let lines: Vec<_> = lines
+            .into_iter()
+            .map(|l| Line::from(Span::raw(l)))
+            .collect();
+        let text = Paragraph::new(lines)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false });
+        f.render_widget(text, chunk);
+    }
+
+    let help = Paragraph::new(Line::from(Span::styled(
+        "LEFT/RIGHT: switch dice, ENTER: back",
+        Style::default().fg(Color::DarkGray),
+    )))
+    .alignment(Alignment::Center);
+    f.render_widget(help, chunks[1]);
+}
+
+fn draw_select_rule_set(f: &mut Frame, app: &App) {
+    let chunk = drwa_logo_and_frame(f);
+    draw_selections_for_rule_set(f, app, chunk);
+}
+
 fn draw_select_number_of_players(f: &mut Frame, app: &App) {
     let chunk = drwa_logo_and_frame(f);
     draw_selections_for_number_of_players(f, app, chunk);
 }
 
+fn draw_select_seats(f: &mut Frame, app: &App) {
+    let chunk = drwa_logo_and_frame(f);
+    draw_selections_for_seats(f, app, chunk);
+}
+
 fn drwa_logo_and_frame(f: &mut Frame) -> Rect {
     /* Draw frame border */
     let chunk = Layout::default()
@@ -141,7 +239,93 @@ fn draw_start_menu_selections(f: &mut Frame, app: &App, chunk: Rect) {
     let AppState::StartMenu(pos) = app.get_state() else {
         panic!("Unexpected state")
     };
-    let choices = [StartMenuSelection::Play, StartMenuSelection::Exit];
+    let choices = [
+        StartMenuSelection::Play,
+        StartMenuSelection::Daily,
+        StartMenuSelection::Statistics,
+        StartMenuSelection::Options,
+        StartMenuSelection::Exit,
+    ];
+    let choices: Vec<_> = choices
+        .iter()
+        .map(|c| {
+            Line::from(Span::styled(
+                format!("{}", c),
+                if pos == c {
+                    Style::default().fg(Color::DarkGray).bg(Color::White)
+                } else {
+                    Style::default()
+                },
+            ))
+        })
+        .collect();
+    draw_selections(f, chunk, choices);
+}
+
+fn draw_selections_for_options(f: &mut Frame, app: &App, chunk: Rect) {
+    let AppState::Options(pos) = app.get_state() else {
+        panic!("Unexpected state")
+    };
+    let settings = app.get_settings();
+    let preferences = app.get_preferences();
+    let choices: Vec<_> = enum_iterator::all::<OptionsSelection>()
+        .map(|c| {
+            let text = match c {
+                OptionsSelection::RollsPerTurn => format!("{}: < {} >", c, settings.rolls_per_turn),
+                OptionsSelection::CarryOverRolls => {
+                    format!("{}: {}", c, on_or_off(settings.carries_over_rolls))
+                }
+                OptionsSelection::PhysicalDice => {
+                    format!("{}: {}", c, on_or_off(settings.uses_physical_dice))
+                }
+                OptionsSelection::FairDice => match app.get_exchange() {
+                    Some(exchange) if settings.uses_fair_dice => {
+                        format!("{}: Shared by {}", c, exchange.get_num_participants())
+                    }
+                    None if settings.uses_fair_dice => format!("{}: This machine only", c),
+                    _ => format!("{}: Off", c),
+                },
+                OptionsSelection::Dice => format!("{}: < {} >", c, settings.die),
+                OptionsSelection::Arrangement => {
+                    format!("{}: < {} >", c, preferences.arrangement)
+                }
+                OptionsSelection::RollAnimation => {
+                    format!("{}: < {} ms >", c, preferences.roll_animation.as_millis())
+                }
+                OptionsSelection::ReducedMotion => {
+                    format!("{}: {}", c, on_or_off(preferences.reduces_motion))
+                }
+                OptionsSelection::Back => format!("{}", c),
+            };
+            Line::from(Span::styled(
+                text,
+                if pos == &c {
+                    Style::default().fg(Color::DarkGray).bg(Color::White)
+                } else {
+                    Style::default()
+                },
+            ))
+        })
+        .collect();
+    draw_selections(f, chunk, choices);
+}
+
+fn on_or_off(flag: bool) -> &'static str {
+    if flag {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+fn draw_selections_for_rule_set(f: &mut Frame, app: &App, chunk: Rect) {
+    let AppState::SelectRuleSet(pos) = app.get_state() else {
+        panic!("Unexpected state")
+    };
+    let choices: Vec<_> = enum_iterator::all::<Variant>()
+        .map(RuleSetSelection::Variant)
+        .chain([RuleSetSelection::Back])
+        .collect();
     let choices: Vec<_> = choices
         .iter()
         .map(|c| {
@@ -182,6 +366,32 @@ fn draw_selections_for_number_of_players(f: &mut Frame, app: &App, chunk: Rect)
     draw_selections(f, chunk, choices);
 }
 
+fn draw_selections_for_seats(f: &mut Frame, app: &App, chunk: Rect) {
+    let AppState::SelectSeats(pos) = app.get_state() else {
+        panic!("Unexpected state")
+    };
+    let seats = app.get_seats();
+    let choices: Vec<_> = (0..seats.len())
+        .map(SeatsSelection::Seat)
+        .chain([SeatsSelection::Start, SeatsSelection::Back])
+        .map(|c| {
+            let text = match c {
+                SeatsSelection::Seat(pid) => format!("{}: < {} >", c, seats[pid]),
+                _ => format!("{}", c),
+            };
+            Line::from(Span::styled(
+                text,
+                if pos == &c {
+                    Style::default().fg(Color::DarkGray).bg(Color::White)
+                } else {
+                    Style::default()
+                },
+            ))
+        })
+        .collect();
+    draw_selections(f, chunk, choices);
+}
+
 fn draw_selections<'a, I>(f: &mut Frame, chunk: Rect, choices: I)
 where
     I: IntoIterator,
@@ -191,12 +401,20 @@ where
     let choice_height = 1;
     let max_choice_str_len = choices.iter().map(|c| c.width()).max().unwrap() as u16;
     let rect_width = max_choice_str_len + SELECTION_MARGIN * 2;
+    /* Choices are separated by blank lines unless there are too many of them to fit */
+    let step = if (2 * choices.len() - 1) as u16 * choice_height <= chunk.height {
+        2
+    } else {
+        1
+    };
     let choice_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints((0..(2 * choices.len() - 1)).map(|_| Constraint::Length(choice_height)))
+        .constraints(
+            (0..(step * choices.len() - (step - 1))).map(|_| Constraint::Length(choice_height)),
+        )
         .split(create_centerd_rect(chunk, rect_width, chunk.height));
 
-    for (choice, &chunk) in choices.into_iter().zip(choice_chunks.iter().step_by(2)) {
+    for (choice, &chunk) in choices.into_iter().zip(choice_chunks.iter().step_by(step)) {
         let text = Paragraph::new(choice)
             .block(Block::default())
             .alignment(Alignment::Center);
@@ -216,6 +434,20 @@ fn draw_play_ui(f: &mut Frame, app: &App) {
 }
 
 fn draw_playing_ground(f: &mut Frame, app: &App, chunk: Rect) {
+    /* The odds only take the room which the dice do not need */
+    let dice_num = app.get_game_data().unwrap().get_rule_set().dice_num() as u16;
+    let dice_width = (DICE_STR_WIDTH as u16 + DICE_MARGIN * 2) * dice_num
+        + HAND_MARGIN * (dice_num - 1)
+        + PLAYING_GROUND_MARGIN * 2;
+    let odds_width = if app.shows_odds() && chunk.width >= dice_width + ODDS_PANEL_WIDTH {
+        ODDS_PANEL_WIDTH
+    } else {
+        0
+    };
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(odds_width)])
+        .split(chunk);
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -223,11 +455,57 @@ fn draw_playing_ground(f: &mut Frame, app: &App, chunk: Rect) {
             Constraint::Percentage(50),
             Constraint::Percentage(30),
         ])
-        .split(chunk);
+        .split(columns[0]);
 
     draw_roll_block(f, app, chunks[0]);
     draw_hand_block(f, app, chunks[1]);
     draw_dust_block(f, app, chunks[2]);
+    if odds_width > 0 {
+        draw_odds_panel(f, app, columns[1]);
+    }
+}
+
+/* The chance and the expected score of each box if the player chases it for the rest of the turn */
+fn draw_odds_panel(f: &mut Frame, app: &App, chunk: Rect) {
+    let rule = app.get_game_data().unwrap().get_rule_set();
+    let rows: Vec<_> = app
+        .get_turn_odds()
+        .iter()
+        .map(|o| {
+            let style = if o.chance == 0.0 {
+                Style::default().fg(Color::DarkGray)
+            } else if o.chance == 1.0 {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                Cell::from(rule.box_name(o.b)),
+                Cell::from(format!("{:>5.1}%", o.chance * 100.0)),
+                Cell::from(format!("{:>5.1}", o.expected_score)),
+            ])
+            .style(style)
+        })
+        .collect();
+    let header = Row::new(vec![
+        Cell::from(""),
+        Cell::from("Chance"),
+        Cell::from("Score"),
+    ]);
+    let widths = [
+        Constraint::Length(ODDS_BOX_WIDTH),
+        Constraint::Length(6),
+        Constraint::Length(5),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .title("Odds (O to hide)")
+                .borders(Borders::ALL),
+        )
+        .column_spacing(1);
+    f.render_widget(table, chunk);
 }
 
 fn draw_roll_block(f: &mut Frame, app: &App, chunk: Rect) {
@@ -237,8 +515,25 @@ fn draw_roll_block(f: &mut Frame, app: &App, chunk: Rect) {
     let roll_button_chunk = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(100)])
-        .split(create_centerd_rect(chunk, 13, 3));
-    let text = Paragraph::new(Line::from(Span::styled("Roll!", Style::default())))
+        .split(create_centerd_rect(chunk, 18, 3));
+    let label = match app.get_state().get_play_data() {
+        Ok(play) if matches!(play.get_phase(), PlayPhase::Announce) => String::from("Skip"),
+        Ok(play) if play.is_manual() && matches!(play.get_phase(), PlayPhase::Roll(..)) => {
+            if play.is_entry_complete() {
+                String::from("Confirm")
+            } else {
+                String::from("Type the pips")
+            }
+        }
+        Ok(play)
+            if matches!(play.get_phase(), PlayPhase::Roll(..)) && !play.is_entry_complete() =>
+        {
+            String::from("Waiting...")
+        }
+        Ok(play) => format!("Roll! ({} left)", play.get_remaining_rolls()),
+        _ => String::from("Roll!"),
+    };
+    let text = Paragraph::new(Line::from(Span::styled(label, Style::default())))
         .block(Block::default().borders(Borders::ALL))
         .style(match app.get_state().get_play_cursor_pos().unwrap() {
             PlayCursorPos::Roll => Style::default().fg(Color::DarkGray).bg(Color::White),
@@ -254,37 +549,67 @@ fn draw_hand_block(f: &mut Frame, app: &App, chunk: Rect) {
 
     match app.get_state().get_play_data() {
         Ok(play) => {
-            let dice = HandOpError::unwrap_pips(play.get_hand().get_pips());
+            let game_data = app.get_game_data().unwrap();
+            let pid = play.get_player_id();
+            let hand = play.get_hand();
+            let dice = hand.get_pips();
+            let dice_chunks = split_dice_chunks(chunk, hand.get_dice_num(), HAND_MARGIN);
+            let entered = match play.get_phase() {
+                PlayPhase::Roll(..) => play.get_entered_pips(),
+                _ => None,
+            };
+            if let Some(entered) = entered {
+                draw_hint(f, pips_entry_hint(play), chunk);
+                /* The entered pips fill the slots not held from the left */
+                let mut entered = entered.iter();
+                for i in 0..hand.get_dice_num() {
+                    if hand.is_held(i).unwrap() {
+                        continue;
+                    }
+                    let text = match entered.next() {
+                        Some(&pip) => Pip::new(pip)
+                            .map_or_else(|_| vec![], |p| die_face_lines(hand.get_die_spec(), p)),
+                        None => vec![Line::from(""), Line::from("?")],
+                    };
+                    let text = Paragraph::new(text)
+                        .block(Block::default().borders(Borders::ALL))
+                        .alignment(Alignment::Center);
+                    f.render_widget(text, dice_chunks[2 * i]);
+                }
+            } else if matches!(play.get_phase(), PlayPhase::Roll(..)) {
+                draw_hint(f, Span::raw("Enter to stop the dice"), chunk);
+            } else if let Seat::Bot(difficulty) = game_data.get_seat(pid) {
+                let name = game_data.get_player_name(pid);
+                /* Only the near-optimal bots wait for the solver */
+                let hint = if difficulty == Difficulty::NearOptimal && app.is_bot_thinking() {
+                    format!("{} is thinking", name)
+                } else {
+                    format!("{} is playing", name)
+                };
+                draw_hint(f, Span::raw(hint), chunk);
+            } else if app.shows_hints() {
+                draw_hint(f, Span::raw(solver_hint(app)), chunk);
+            } else if matches!(
+                app.get_state().get_play_cursor_pos().unwrap(),
+                PlayCursorPos::Hand(..) | PlayCursorPos::Dust(..)
+            ) {
+                draw_hint(f, Span::raw("< / > to move the die"), chunk);
+            } else if app.can_hint()
+                && matches!(
+                    play.get_phase(),
+                    PlayPhase::SelectOrReroll(..) | PlayPhase::Select
+                )
+            {
+                draw_hint(f, Span::raw("H for a hint"), chunk);
+            }
             for (i, d) in dice.iter().enumerate() {
-                let hand = play.get_hand();
-                let dice_width = DICE_STR_WIDTH as u16 + DICE_MARGIN * 2;
-                let dice_num = Hand::DICE_NUM as u16;
-                let rect_width = dice_width * dice_num + HAND_MARGIN * (dice_num - 1);
-                let rect_height = DICE_STR_HEIGHT as u16 + HAND_MARGIN * 2;
-                let dice_chunks = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([
-                        Constraint::Length(dice_width),
-                        Constraint::Length(HAND_MARGIN),
-                        Constraint::Length(dice_width),
-                        Constraint::Length(HAND_MARGIN),
-                        Constraint::Length(dice_width),
-                        Constraint::Length(HAND_MARGIN),
-                        Constraint::Length(dice_width),
-                        Constraint::Length(HAND_MARGIN),
-                        Constraint::Length(dice_width),
-                    ])
-                    .split(create_centerd_rect(chunk, rect_width, rect_height));
-
+                if entered.is_some() && !hand.is_held(i).unwrap() {
+                    continue;
+                }
                 let text = match (play.get_phase(), hand.is_held(i).unwrap()) {
-                    (PlayPhase::Roll(..), ..) | (.., true) => (0..DICE_STR_HEIGHT)
-                        .map(|h| {
-                            Line::from(Span::styled(
-                                DICE_STR[(d - 1) as usize][h],
-                                Style::default(),
-                            ))
-                        })
-                        .collect(),
+                    (PlayPhase::Roll(..), ..) | (.., true) => {
+                        die_face_lines(hand.get_die_spec(), *d)
+                    }
                     _ => vec![],
                 };
                 let text = Paragraph::new(text)
@@ -294,17 +619,11 @@ fn draw_hand_block(f: &mut Frame, app: &App, chunk: Rect) {
                         }
                         _ => Block::default(),
                     })
-                    .style(match app.get_state().get_play_cursor_pos().unwrap() {
-                        &PlayCursorPos::Hand(pos) => {
-                            if i == pos {
-                                Style::default().fg(Color::DarkGray).bg(Color::White)
-                            } else {
-                                Style::default()
-                            }
-                        }
-
-                        _ => Style::default(),
-                    })
+                    .style(die_style(
+                        app,
+                        i,
+                        app.get_state().get_play_cursor_pos().unwrap() == &PlayCursorPos::Hand(i),
+                    ))
                     .alignment(Alignment::Center);
                 f.render_widget(text, dice_chunks[2 * i]);
             }
@@ -316,61 +635,117 @@ fn draw_hand_block(f: &mut Frame, app: &App, chunk: Rect) {
     }
 }
 
+/* Pips are drawn when the die has them, otherwise the label is shown in the middle */
+fn die_face_lines(die: &DieSpec, pip: Pip) -> Vec<Line<'static>> {
+    if die.shows_pips && pip.value() as usize <= DICE_KINDS {
+        DICE_STR[(pip.value() - 1) as usize]
+            .iter()
+            .map(|&s| Line::from(Span::styled(s, Style::default())))
+            .collect()
+    } else {
+        let label = die.label_of(pip).unwrap_or("?");
+        vec![Line::from(""), Line::from(label), Line::from("")]
+    }
+}
+
+fn pips_entry_hint(play: &Play) -> Span<'static> {
+    match play.get_rejected_pip() {
+        Some(pip) => Span::styled(
+            format!("{} is not a pip of a die", pip),
+            Style::default().fg(Color::Red),
+        ),
+        None => {
+            let die = play.get_hand().get_die_spec();
+            let faces = if die.shows_pips {
+                let values: Vec<_> = die.faces.iter().map(|f| f.value).collect();
+                format!("{}-{}", values[0], values[values.len() - 1])
+            } else {
+                let faces: Vec<_> = die
+                    .faces
+                    .iter()
+                    .map(|f| format!("{}={}", f.value, f.label))
+                    .collect();
+                faces.join(" ")
+            };
+            Span::raw(format!("Type the pips ({}), BACKSPACE to erase", faces))
+        }
+    }
+}
+
+/* The dice which the hint recommends to hold are green wherever they are */
+fn die_style(app: &App, pos: usize, is_cursor: bool) -> Style {
+    let is_recommended = app.get_hint().is_some_and(|h| h.is_held(pos));
+    match (is_recommended, is_cursor) {
+        (true, true) => Style::default().fg(Color::Green).bg(Color::White),
+        (true, false) => Style::default().fg(Color::Green),
+        (false, true) => Style::default().fg(Color::DarkGray).bg(Color::White),
+        (false, false) => Style::default(),
+    }
+}
+
+/* The solver's advice with the expected final scores */
+fn solver_hint(app: &App) -> String {
+    let Some(hint) = app.get_hint() else {
+        return if app.can_hint() {
+            String::from("Working out the hint")
+        } else {
+            String::from("No hints for this game")
+        };
+    };
+    match (hint.get_hold_values(), hint.get_best_box()) {
+        (Some((best, yours)), ..) if !hint.scores_now() => format!(
+            "Hold the green dice to expect {:.1} (yours {:.1})",
+            best, yours
+        ),
+        (.., Some((b, value))) => {
+            let rule = app.get_game_data().unwrap().get_rule_set();
+            format!("Score {} to expect {:.1}", rule.box_name(b), value)
+        }
+        _ => String::new(),
+    }
+}
+
+/* A line of help at the bottom of the block */
+fn draw_hint(f: &mut Frame, hint: Span, chunk: Rect) {
+    let hint_chunk = Rect {
+        y: chunk.bottom().saturating_sub(2),
+        height: 1,
+        ..chunk.inner(&Margin {
+            horizontal: 1,
+            vertical: 0,
+        })
+    };
+    f.render_widget(
+        Paragraph::new(Line::from(hint)).alignment(Alignment::Center),
+        hint_chunk,
+    );
+}
+
 fn draw_dust_block(f: &mut Frame, app: &App, chunk: Rect) {
     let block = Block::default().title("Dust").borders(Borders::ALL);
     f.render_widget(block, chunk);
 
     match app.get_state().get_play_data() {
         Ok(play) => {
-            let dice_width = DICE_STR_WIDTH as u16 + DICE_MARGIN * 2;
-            let dice_num = Hand::DICE_NUM as u16;
-            let rect_width = dice_width * dice_num + DUST_MARGIN * (dice_num - 1);
-            let rect_height = DICE_STR_HEIGHT as u16 + DUST_MARGIN * 2;
-            let dice_chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([
-                    Constraint::Length(dice_width),
-                    Constraint::Length(DUST_MARGIN),
-                    Constraint::Length(dice_width),
-                    Constraint::Length(DUST_MARGIN),
-                    Constraint::Length(dice_width),
-                    Constraint::Length(DUST_MARGIN),
-                    Constraint::Length(dice_width),
-                    Constraint::Length(DUST_MARGIN),
-                    Constraint::Length(dice_width),
-                ])
-                .split(create_centerd_rect(chunk, rect_width, rect_height));
-
-            let dice = HandOpError::unwrap_pips(play.get_hand().get_pips());
+            let hand = play.get_hand();
+            let dice_chunks = split_dice_chunks(chunk, hand.get_dice_num(), DUST_MARGIN);
+
+            let dice = hand.get_pips();
             for (i, d) in dice.iter().enumerate() {
-                let hand = play.get_hand();
                 let text = match (play.get_phase(), hand.is_held(i).unwrap()) {
                     (PlayPhase::Roll(..), ..) | (.., true) => vec![],
-                    _ => (0..DICE_STR_HEIGHT)
-                        .map(|h| {
-                            Line::from(Span::styled(
-                                DICE_STR[(d - 1) as usize][h],
-                                Style::default(),
-                            ))
-                        })
-                        .collect(),
+                    _ => die_face_lines(hand.get_die_spec(), *d),
                 };
                 let text = Paragraph::new(text)
                     .block(match (play.get_phase(), hand.is_held(i).unwrap()) {
                         (PlayPhase::Roll(..), ..) | (.., true) => Block::default(),
                         _ => Block::default().borders(Borders::ALL),
                     })
-                    .style(match app.get_state().get_play_cursor_pos().unwrap() {
-                        &PlayCursorPos::Dust(pos) => {
-                            if i == pos {
-                                Style::default().fg(Color::DarkGray).bg(Color::White)
-                            } else {
-                                Style::default()
-                            }
-                        }
-
-                        _ => Style::default(),
-                    })
+                    .style(die_style(
+                        app,
+                        i,
+                        app.get_state().get_play_cursor_pos().unwrap() == &PlayCursorPos::Dust(i),
+                    ))
                     .alignment(Alignment::Center);
                 f.render_widget(text, dice_chunks[2 * i]);
             }
@@ -389,155 +764,309 @@ fn draw_score_table(f: &mut Frame, app: &App, chunk: Rect) {
             _ => false,
         }
     };
-    let dislay_dice = |pid: usize| -> Option<Vec<u32>> {
+    let dislay_dice = |pid: usize| -> Option<Dice> {
         match app.get_state().get_play_data() {
-            Ok(p) if is_playing(pid) => Some(HandOpError::unwrap_pips(p.get_hand().get_pips())),
+            Ok(p) if is_playing(pid) => p.get_hand().get_dice().ok(),
+            _ => None,
+        }
+    };
+    let turn = app
+        .get_state()
+        .get_play_data()
+        .map_or(TurnContext::new(0), |p| p.get_turn_context());
+    let is_announcing = matches!(
+        app.get_state().get_play_data().map(|p| p.get_phase()),
+        Ok(PlayPhase::Announce)
+    );
+    /* The box under the cursor if it is in the given column */
+    let cursor_box = |col: usize| -> Option<Boxes> {
+        match app.get_state().get_play_cursor_pos() {
+            Ok(&PlayCursorPos::Table(b, c)) if c == col => Some(b),
             _ => None,
         }
     };
 
-    let mut score_rows: Vec<_> = enum_iterator::all::<Boxes>()
-        .map(|b| {
-            Row::new(
-                vec![Cell::from(format!("{:>1$}", b, BOXES_CELL_WIDTH))]
-                    .into_iter()
-                    .chain(
-                        (0..app.get_game_data().unwrap().get_num_players()).map(|pid| {
-                            let st = &app.get_game_data().unwrap().get_score_table(pid);
-                            let is_playing = is_playing(pid);
-                            let dice = dislay_dice(pid);
-
-                            let text = if st.has_score_in(b) {
-                                format!("{:>1$}", st.get_score(b).unwrap(), SCORE_CELL_WIDTH)
-                            } else if let Some(d) = dice {
-                                format!("{:>1$}", scoring(b, &d), SCORE_CELL_WIDTH)
-                            } else {
-                                String::new()
-                            };
+    let game_data = app.get_game_data().unwrap();
+    let rule = game_data.get_rule_set();
+    let columns = rule.columns();
+    let is_multi_column = columns.len() > 1;
+    let is_multi_entry = rule.entries_per_box() > 1;
+    let cell_width = if is_multi_column {
+        COLUMN_CELL_WIDTH
+    } else if is_multi_entry {
+        ENTRIES_CELL_WIDTH
+    } else {
+        SCORE_CELL_WIDTH
+    };
+    /* Each player has a cell for each column of the score sheet */
+    let score_cells: Vec<_> = (0..game_data.get_num_players())
+        .flat_map(|pid| {
+            (0..game_data.get_score_table(pid).get_num_columns()).map(move |col| (pid, col))
+        })
+        .collect();
+    let score_row = |name: &str, cell: &dyn Fn(usize, usize) -> Cell<'static>| {
+        Row::new(
+            vec![Cell::from(format!("{:>1$}", name, BOXES_CELL_WIDTH))]
+                .into_iter()
+                .chain(score_cells.iter().map(|&(pid, col)| cell(pid, col))),
+        )
+    };
+
+    let mut score_rows: Vec<_> = rule
+        .boxes()
+        .iter()
+        .map(|&b| {
+            score_row(&rule.box_name(b), &|pid, col| {
+                let score_table = game_data.get_score_table(pid);
+                let st = score_table.get_column(col);
+                let is_playing = is_playing(pid);
+                let dice = dislay_dice(pid);
+
+                let can_fill = match &dice {
+                    Some(..) if is_announcing => score_table.can_announce(col, b),
+                    Some(d) => score_table.can_fill(col, b, d),
+                    None => false,
+                };
 
-                            let style = if is_playing && !st.has_score_in(b) {
-                                let pos = app.get_state().get_play_cursor_pos().unwrap();
-                                let mut style = Style::default().fg(Color::Rgb(255, 215, 0));
-                                if pos == &PlayCursorPos::Table(b) {
-                                    style = style.fg(Color::Black).bg(Color::Rgb(255, 215, 0));
+                let hint = app.get_hint().filter(|_| is_playing && can_fill);
+                let hint_value = hint.and_then(|h| h.get_box_value(b));
+                let is_recommended = hint
+                    .and_then(|h| h.get_best_box())
+                    .is_some_and(|(x, ..)| x == b);
+                let style = if is_playing && can_fill {
+                    let color = if is_recommended {
+                        Color::Green
+                    } else {
+                        Color::Rgb(255, 215, 0)
+                    };
+                    let mut style = Style::default().fg(color);
+                    if cursor_box(col) == Some(b) {
+                        style = style.fg(Color::Black).bg(color);
+                    }
+                    if score_table.get_announced_box() == Some(b) {
+                        style = style.add_modifier(Modifier::UNDERLINED);
+                    }
+                    style
+                } else {
+                    Style::default()
+                };
+
+                /* Each entry of the box is shown in a row, followed by the awarded points */
+                if is_multi_entry {
+                    let next_entry = st.get_entries(b).iter().position(|e| e.is_none());
+                    let mut spans: Vec<_> = st
+                        .get_entries(b)
+                        .iter()
+                        .enumerate()
+                        .flat_map(|(i, e)| {
+                            let entry = match (e, &dice) {
+                                (Some(score), ..) => Span::raw(format!("{:>2}", score)),
+                                (None, Some(d)) if can_fill && next_entry == Some(i) => {
+                                    Span::styled(format!("{:>2}", st.score_of(b, d, &turn)), style)
                                 }
-                                style
-                            } else {
-                                Style::default()
+                                _ => Span::raw(format!("{:>2}", "-")),
                             };
+                            [entry, Span::raw(" ")]
+                        })
+                        .collect();
+                    let award = st.get_award(b);
+                    spans.push(if award > 0 {
+                        Span::styled(format!("{:>+3}", award), Style::default().fg(Color::Green))
+                    } else {
+                        Span::raw("")
+                    });
+                    return Cell::from(Line::from(spans));
+                }
 
-                            Cell::from(text).style(style)
-                        }),
-                    ),
-            )
+                let text = if st.has_score_in(b) {
+                    format!("{:>1$}", st.get_score(b).unwrap(), cell_width)
+                } else if let (true, Some(d)) = (can_fill, &dice) {
+                    let score = st.score_of(b, d, &turn);
+                    /* The expected final score comes before the score of the box */
+                    match hint_value {
+                        Some(v) => format!("{:>1$}", format!("({:.1}) {}", v, score), cell_width),
+                        None => format!("{:>1$}", score, cell_width),
+                    }
+                } else {
+                    String::new()
+                };
+
+                Cell::from(text).style(style)
+            })
         })
         .collect();
-    let bonus_cell = Row::new(
-        vec![Cell::from(format!("{:>1$}", "Bonus", BOXES_CELL_WIDTH))]
-            .into_iter()
-            .chain(
-                (0..app.get_game_data().unwrap().get_num_players()).map(|pid| {
-                    let st = &app.get_game_data().unwrap().get_score_table(pid);
-                    let is_playing = is_playing(pid);
-                    let dice = dislay_dice(pid);
-
-                    let default_bstext = format!("{:>2}", "");
-                    let default_bsstyle = Style::default();
-                    let (bstext, bsstyle) = if let Some(score) = st.calculate_bonus() {
-                        (format!("{:>2}", score), default_bsstyle)
-                    } else if let (true, Some(d)) = (is_playing, &dice) {
-                        let pos = app.get_state().get_play_cursor_pos().unwrap();
-                        let ifbs = if let &PlayCursorPos::Table(b) = pos {
-                            st.calculate_bonus_if_filled_by(b, scoring(b, d))
-                        } else {
-                            None
-                        };
-
-                        if let Some(bs) = ifbs {
-                            (
-                                format!("{:>2}", bs),
-                                default_bsstyle.fg(Color::Rgb(255, 215, 0)),
-                            )
-                        } else {
-                            (default_bstext, default_bsstyle)
-                        }
-                    } else {
-                        (default_bstext, default_bsstyle)
-                    };
+    if let Some(ub) = rule.upper_bonus() {
+        let bonus_cell = score_row("Bonus", &|pid, col| {
+            let st = game_data.get_score_table(pid).get_column(col);
+            let is_playing = is_playing(pid);
+            let dice = dislay_dice(pid);
 
-                    let us = st.get_total_upper_score();
-                    let default_ustext = format!("{:>3}", us);
-                    let default_usstyle = Style::default();
-                    let (ustext, usstyle) = if let (true, Some(d)) = (is_playing, &dice) {
-                        let pos = app.get_state().get_play_cursor_pos().unwrap();
-                        let ifus = if let &PlayCursorPos::Table(b) = pos {
-                            st.get_total_upper_score_if_filled_by(b, scoring(b, d))
-                        } else {
-                            us
-                        };
-
-                        if ifus > us {
-                            (
-                                format!("{:>3}", ifus),
-                                default_usstyle.fg(Color::Rgb(255, 215, 0)),
-                            )
-                        } else {
-                            (default_ustext, default_usstyle)
-                        }
-                    } else {
-                        (default_ustext, default_usstyle)
-                    };
+            let default_bstext = format!("{:>2}", "");
+            let default_bsstyle = Style::default();
+            let (bstext, bsstyle) = if let Some(score) = st.calculate_bonus() {
+                (format!("{:>2}", score), default_bsstyle)
+            } else if let (true, Some(d)) = (is_playing, &dice) {
+                let ifbs = if let Some(b) = cursor_box(col) {
+                    st.calculate_bonus_if_filled_by(b, st.score_of(b, d, &turn))
+                } else {
+                    None
+                };
 
-                    Cell::from(Line::from(vec![
-                        Span::styled(bstext, bsstyle),
-                        Span::raw(" ("),
-                        Span::styled(ustext, usstyle),
-                        Span::raw(format!("/{:>2})", ScoreTable::BONUS_THRESHOLD)),
-                    ]))
-                }),
-            ),
-    );
-    score_rows.push(bonus_cell);
-    let total_cell = Row::new(
-        vec![Cell::from(format!("{:>1$}", "Total", BOXES_CELL_WIDTH))]
-            .into_iter()
-            .chain(
-                (0..app.get_game_data().unwrap().get_num_players()).map(|pid| {
-                    let st = &app.get_game_data().unwrap().get_score_table(pid);
-                    let is_playing = is_playing(pid);
-                    let dice = dislay_dice(pid);
-                    let total_score = st.get_total_score();
-
-                    let default_text = format!("{:>1$}", total_score, SCORE_CELL_WIDTH);
-                    let default_style = Style::default();
-                    let (text, style) = if let (true, Some(d)) = (is_playing, &dice) {
-                        let pos = app.get_state().get_play_cursor_pos().unwrap();
-                        let if_total_score = if let &PlayCursorPos::Table(b) = pos {
-                            st.get_total_score_if_filled_by(b, scoring(b, d))
-                        } else {
-                            total_score
-                        };
-
-                        if if_total_score > total_score {
-                            (
-                                format!("{:>1$}", if_total_score, SCORE_CELL_WIDTH),
-                                default_style.fg(Color::Rgb(255, 215, 0)),
-                            )
-                        } else {
-                            (default_text, default_style)
-                        }
-                    } else {
-                        (default_text, default_style)
-                    };
+                if let Some(bs) = ifbs {
+                    (
+                        format!("{:>2}", bs),
+                        default_bsstyle.fg(Color::Rgb(255, 215, 0)),
+                    )
+                } else {
+                    (default_bstext, default_bsstyle)
+                }
+            } else {
+                (default_bstext, default_bsstyle)
+            };
 
-                    Cell::from(text).style(style)
-                }),
-            ),
-    );
+            let us = st.get_total_upper_score();
+            let default_ustext = format!("{:>3}", us);
+            let default_usstyle = Style::default();
+            let (ustext, usstyle) = if let (true, Some(d)) = (is_playing, &dice) {
+                let ifus = if let Some(b) = cursor_box(col) {
+                    st.get_total_upper_score_if_filled_by(b, st.score_of(b, d, &turn))
+                } else {
+                    us
+                };
+
+                if ifus > us {
+                    (
+                        format!("{:>3}", ifus),
+                        default_usstyle.fg(Color::Rgb(255, 215, 0)),
+                    )
+                } else {
+                    (default_ustext, default_usstyle)
+                }
+            } else {
+                (default_ustext, default_usstyle)
+            };
+
+            /* Narrow cells have no room for the threshold */
+            if is_multi_column {
+                return Cell::from(Line::from(vec![
+                    Span::styled(bstext, bsstyle),
+                    Span::raw("("),
+                    Span::styled(ustext, usstyle),
+                    Span::raw(")"),
+                ]));
+            }
+            Cell::from(Line::from(vec![
+                Span::styled(bstext, bsstyle),
+                Span::raw(" ("),
+                Span::styled(ustext, usstyle),
+                Span::raw(format!("/{:>2})", ub.threshold)),
+            ]))
+        });
+        score_rows.push(bonus_cell);
+    }
+    if let Some(ybp) = rule.yahtzee_bonus_point() {
+        let yahtzee_bonus_cell = score_row("Yahtzee bonus", &|pid, col| {
+            let st = game_data.get_score_table(pid).get_column(col);
+            let is_playing = is_playing(pid);
+            let dice = dislay_dice(pid);
+
+            let (count, style) = match (is_playing, &dice) {
+                (true, Some(d))
+                    if st.is_joker(d) && (!is_multi_column || cursor_box(col).is_some()) =>
+                {
+                    (
+                        st.get_yahtzee_bonus_count() + 1,
+                        Style::default().fg(Color::Rgb(255, 215, 0)),
+                    )
+                }
+                _ => (st.get_yahtzee_bonus_count(), Style::default()),
+            };
+            let text = if is_multi_column {
+                format!("{:>1$}", count * ybp, cell_width)
+            } else {
+                format!(
+                    "{:>1$}",
+                    format!("{} (x{})", count * ybp, count),
+                    cell_width
+                )
+            };
+
+            Cell::from(text).style(style)
+        });
+        score_rows.push(yahtzee_bonus_cell);
+    }
+    let total_cell = score_row("Total", &|pid, col| {
+        let st = game_data.get_score_table(pid);
+        let is_playing = is_playing(pid);
+        let dice = dislay_dice(pid);
+        let total_score = st.get_column_total_score(col);
+
+        let default_text = format!("{:>1$}", total_score, cell_width);
+        let default_style = Style::default();
+        let (text, style) = if let (true, Some(d)) = (is_playing, &dice) {
+            let if_total_score = if let Some(b) = cursor_box(col) {
+                st.get_column_total_score_if_filled_with(col, b, d, &turn)
+            } else {
+                total_score
+            };
+
+            if if_total_score > total_score {
+                (
+                    format!("{:>1$}", if_total_score, cell_width),
+                    default_style.fg(Color::Rgb(255, 215, 0)),
+                )
+            } else {
+                (default_text, default_style)
+            }
+        } else {
+            (default_text, default_style)
+        };
+
+        Cell::from(text).style(style)
+    });
     score_rows.push(total_cell);
+    if is_multi_column {
+        /* The weighted sum of the column totals is shown under the last column */
+        let grand_total_cell = score_row("Grand total", &|pid, col| {
+            if col + 1 < columns.len() {
+                return Cell::from(String::new());
+            }
+            let st = game_data.get_score_table(pid);
+            let total_score = st.get_total_score();
+            let if_total_score = match (dislay_dice(pid), app.get_state().get_play_cursor_pos()) {
+                (Some(d), Ok(&PlayCursorPos::Table(b, c))) => {
+                    st.get_total_score_if_filled_with(c, b, &d, &turn)
+                }
+                _ => total_score,
+            };
+
+            if if_total_score > total_score {
+                Cell::from(format!("{:>1$}", if_total_score, cell_width))
+                    .style(Style::default().fg(Color::Rgb(255, 215, 0)))
+            } else {
+                Cell::from(format!("{:>1$}", total_score, cell_width))
+            }
+        });
+        score_rows.push(grand_total_cell);
+    }
+    if !rule.category_awards().is_empty() {
+        let points_cell = score_row("Points", &|pid, col| {
+            if col + 1 < columns.len() {
+                return Cell::from(String::new());
+            }
+            let points = game_data.get_score_table(pid).get_points().unwrap_or(0);
+            Cell::from(format!("{:>1$}", points, cell_width))
+        });
+        score_rows.push(points_cell);
+    }
     let score_header = Row::new(vec![Cell::from(String::from(""))].into_iter().chain(
-        (0..app.get_game_data().unwrap().get_num_players()).map(|pid| {
-            let text = format!("{:^1$}", format!("Player{}", pid), SCORE_CELL_WIDTH);
+        score_cells.iter().map(|&(pid, col)| {
+            let name = match (is_multi_column, game_data.get_seat(pid)) {
+                (true, Seat::Human) => format!("P{} {}", pid, columns[col].name),
+                (true, Seat::Bot(..)) => format!("B{} {}", pid, columns[col].name),
+                (false, ..) => game_data.get_player_name(pid),
+            };
+            let text = format!("{:^1$}", name, cell_width);
             let style = if is_playing(pid) {
                 Style::default().fg(Color::Black).bg(Color::LightYellow)
             } else {
@@ -546,12 +1075,12 @@ fn draw_score_table(f: &mut Frame, app: &App, chunk: Rect) {
             Cell::from(text).style(style)
         }),
     ));
-    let score_table_width: Vec<_> = (0..(app.get_game_data().unwrap().get_num_players() + 1))
+    let score_table_width: Vec<_> = (0..(score_cells.len() + 1))
         .map(|x| {
             Constraint::Length(if x == 0 {
                 BOXES_CELL_WIDTH as u16
             } else {
-                SCORE_CELL_WIDTH as u16
+                cell_width as u16
             })
         })
         .collect();
@@ -588,7 +1117,7 @@ fn draw_result(f: &mut Frame, app: &App, chunk: Rect) {
     let block = Block::default().borders(Borders::ALL);
     f.render_widget(block, chunk);
 
-    let height = (app.get_game_data().unwrap().get_num_players() as u16) + 2;
+    let height = (app.get_game_data().unwrap().get_num_players() as u16) + 4;
     let width = chunk.width - 4;
 
     let text_chunk = Layout::default()
@@ -596,32 +1125,70 @@ fn draw_result(f: &mut Frame, app: &App, chunk: Rect) {
         .constraints([Constraint::Percentage(100)])
         .split(create_centerd_rect(chunk, width + 2, height + 2));
 
+    /* A player who won outright ranks first, then points rank before the score */
     let mut results: Vec<_> = (0..app.get_game_data().unwrap().get_num_players())
         .map(|i| {
-            (
-                i,
-                app.get_game_data()
-                    .unwrap()
-                    .get_score_table(i)
-                    .get_total_score(),
-            )
+            let st = app.get_game_data().unwrap().get_score_table(i);
+            let points = st.get_points().unwrap_or(0);
+            (i, (st.has_won_outright(), points, st.get_total_score()))
         })
         .collect();
     results.sort_by(|(.., left), (.., right)| left.cmp(right).reverse());
+    let game_data = app.get_game_data().unwrap();
     let mut results: Vec<_> = results
         .iter()
         .map(|(pid, score)| {
             let rank = results.iter().position(|(.., s)| s == score).unwrap() + 1;
             Line::from(Span::styled(
-                format!("{:^1$}", format!("{}. Player{}", rank, pid), width as usize),
+                format!(
+                    "{:^1$}",
+                    format!("{}. {}", rank, game_data.get_player_name(*pid)),
+                    width as usize
+                ),
                 Style::default(),
             ))
         })
         .collect();
+    let seed = match app.get_daily() {
+        Some(daily) => match daily.get_attempt() {
+            Some(attempt) => format!("Daily {}: {}", daily.get_date(), attempt),
+            None => format!("Daily {}: Could not be recorded", daily.get_date()),
+        },
+        None if game_data.get_settings().uses_fair_dice => {
+            if app.is_transcript_saved() {
+                let (kind, file) = match app.get_exchange() {
+                    Some(exchange) => ("fair", exchange.transcript_file()),
+                    None => ("locally committed", TranscriptFile::default()),
+                };
+                format!(
+                    "{} {} rolls saved to {}",
+                    game_data.get_transcript().len(),
+                    kind,
+                    file.get_path().display()
+                )
+            } else {
+                String::from("The transcript could not be saved")
+            }
+        }
+        None => format!("Seed: {}", game_data.get_seed()),
+    };
     results.extend([
         Line::from(Span::raw("")),
         Line::from(Span::styled(
-            format!("{:^1$}", "Press ENTER to exit.", width as usize),
+            format!("{:^1$}", seed, width as usize),
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(Span::raw("")),
+        Line::from(Span::styled(
+            format!(
+                "{:^1$}",
+                if app.can_review() {
+                    "Press R to review the game, ENTER to exit."
+                } else {
+                    "Press ENTER to exit."
+                },
+                width as usize
+            ),
             Style::default(),
         )),
     ]);
@@ -631,6 +1198,133 @@ fn draw_result(f: &mut Frame, app: &App, chunk: Rect) {
     f.render_widget(text, text_chunk[0]);
 }
 
+fn draw_review_ui(f: &mut Frame, app: &App) {
+    /* Distribute the screen */
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(f.size());
+
+    draw_review(f, app, chunks[0]);
+    draw_score_table(f, app, chunks[1]);
+}
+
+fn draw_review(f: &mut Frame, app: &App, chunk: Rect) {
+    let AppState::Review(top) = app.get_state() else {
+        panic!("Unexpected state")
+    };
+
+    let block = Block::default().title("REVIEW").borders(Borders::ALL);
+    f.render_widget(block, chunk);
+
+    let Some(review) = app.get_review() else {
+        let text =
+            Paragraph::new(Line::from("Working out the review")).alignment(Alignment::Center);
+        f.render_widget(text, create_centerd_rect(chunk, chunk.width - 2, 1));
+        return;
+    };
+
+    let game_data = app.get_game_data().unwrap();
+    let num_players = review.get_num_players() as u16;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(num_players + 2),
+            Constraint::Length(1),
+        ])
+        .margin(FRAME_MARGIN)
+        .split(chunk);
+
+    let rule = game_data.get_rule_set();
+    let rows: Vec<_> = review.get_entries()[*top..]
+        .iter()
+        .map(|e| {
+            let loss_style = if e.is_best() {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Red)
+            };
+            Row::new(vec![
+                Cell::from(format!("{:>5}", e.round)),
+                Cell::from(game_data.get_player_name(e.player_id)),
+                Cell::from(dice_text(&e.dice)),
+                Cell::from(format!("{:>5}", e.remaining_rolls)),
+                Cell::from(choice_text(rule, e.chosen)),
+                Cell::from(choice_text(rule, e.best)),
+                Cell::from(format!("{:>5.1}", e.loss)).style(loss_style),
+            ])
+        })
+        .collect();
+    let header = Row::new(vec![
+        "Round", "Player", "Dice", "Rolls", "Chosen", "Best", " Lost",
+    ])
+    .style(Style::default().fg(Color::Black).bg(Color::LightYellow));
+    let widths = [
+        Constraint::Length(5),
+        Constraint::Length(8),
+        Constraint::Length(9),
+        Constraint::Length(5),
+        Constraint::Length(16),
+        Constraint::Length(16),
+        Constraint::Length(5),
+    ];
+    let table = Table::new(rows, widths).header(header).column_spacing(1);
+    f.render_widget(table, chunks[0]);
+
+    let summary: Vec<_> = (0..review.get_num_players())
+        .map(|pid| {
+            Line::from(format!(
+                "{}: {:.0}% accuracy, {:.1} points lost",
+                game_data.get_player_name(pid),
+                review.get_accuracy(pid) * 100.0,
+                review.get_points_lost(pid)
+            ))
+        })
+        .collect();
+    let summary = Paragraph::new(summary)
+        .block(Block::default().borders(Borders::ALL))
+        .alignment(Alignment::Center);
+    f.render_widget(summary, chunks[1]);
+
+    let help = Paragraph::new(Line::from(Span::styled(
+        "UP/DOWN: scroll, ENTER: back",
+        Style::default().fg(Color::DarkGray),
+    )))
+    .alignment(Alignment::Center);
+    f.render_widget(help, chunks[2]);
+}
+
+fn dice_text(dice: &Dice) -> String {
+    let pips: Vec<_> = dice.iter().map(|p| p.to_string()).collect();
+    pips.join(" ")
+}
+
+fn choice_text(rule: &dyn RuleSet, choice: Choice) -> String {
+    match choice {
+        Choice::Hold(dice) if dice.len() == rule.dice_num() => String::from("Score now"),
+        Choice::Hold(dice) if dice.len() == 0 => String::from("Reroll all"),
+        Choice::Hold(dice) => format!("Hold {}", dice_text(&dice)),
+        Choice::Fill(b) => rule.box_name(b),
+    }
+}
+
+fn split_dice_chunks(chunk: Rect, dice_num: usize, margin: u16) -> Rc<[Rect]> {
+    /* Dice are placed on even indices and margins on odd indices. */
+    let dice_width = DICE_STR_WIDTH as u16 + DICE_MARGIN * 2;
+    let num = dice_num as u16;
+    let rect_width = dice_width * num + margin * (num - 1);
+    let rect_height = DICE_STR_HEIGHT as u16 + margin * 2;
+    let constraints: Vec<_> = (0..(2 * dice_num - 1))
+        .map(|i| Constraint::Length(if i % 2 == 0 { dice_width } else { margin }))
+        .collect();
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(create_centerd_rect(chunk, rect_width, rect_height))
+}
+
 fn create_centerd_rect(base_rect: Rect, width: u16, height: u16) -> Rect {
     Rect::new(
         base_rect.x + (base_rect.width - width) / 2,